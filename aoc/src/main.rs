@@ -0,0 +1,628 @@
+mod registry;
+
+use clap::{Args, Parser, Subcommand};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::error;
+use tracing::info;
+use tracing::info_span;
+use tracing::warn;
+use utils::answers::*;
+use utils::bench::*;
+use utils::cli::*;
+use utils::client::Client;
+use utils::examples::*;
+use utils::history::*;
+use utils::input::*;
+use utils::output::*;
+use utils::results::*;
+use utils::snapshot::UPDATE_SNAPSHOTS;
+use utils::solution::*;
+use utils::submit::*;
+
+const SUBMISSION_LOG: &str = ".aoc/submissions.tsv";
+const ANSWERS_FILE: &str = "answers.toml";
+const BENCH_BASELINE: &str = ".aoc/bench.json";
+const HISTORY_FILE: &str = ".aoc/history.jsonl";
+
+#[derive(Parser)]
+#[clap(name = "aoc", about = "Advent of Code 2021 solutions")]
+struct Cli {
+    #[clap(flatten)]
+    output: OutputArgs,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solution for a single day or for all days
+    Run(RunArgs),
+    /// Download missing puzzle inputs
+    Fetch(DaySelection),
+    /// Submit the answer for a single part to the puzzle site
+    Submit(SubmitArgs),
+    /// Extract the example input and answers from a saved puzzle description
+    Examples(ExamplesArgs),
+    /// Check every day against the expected answers in answers.toml
+    Verify,
+    /// Time parsing and both parts, comparing against a saved baseline
+    Bench(BenchArgs),
+    /// Show the answers and timings of earlier runs
+    History(HistoryArgs),
+    /// Run the test suite of the workspace
+    Test(TestArgs),
+}
+
+#[derive(Args)]
+struct DaySelection {
+    /// Day to select
+    #[clap(short, long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    /// Select every registered day
+    #[clap(long)]
+    all: bool,
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[clap(flatten)]
+    days: DaySelection,
+
+    #[clap(flatten)]
+    solve: SolveArgs,
+
+    /// Do not record this run in the history
+    #[clap(long)]
+    no_history: bool,
+
+    /// Warn when a part is this many times slower than its rolling median
+    #[clap(long, default_value = "1.5")]
+    slowdown: f64,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[clap(flatten)]
+    days: DaySelection,
+
+    #[clap(flatten)]
+    input: InputArgs,
+
+    /// Number of measured iterations, at least one
+    #[clap(
+        short = 'n',
+        long,
+        default_value = "10",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    iterations: usize,
+
+    /// Number of iterations to run before measuring
+    #[clap(long, default_value = "1")]
+    warmup: usize,
+
+    /// Baseline file to compare against
+    #[clap(long, default_value = BENCH_BASELINE)]
+    baseline: PathBuf,
+
+    /// Store the results as the new baseline
+    #[clap(long)]
+    save: bool,
+
+    /// Relative slowdown of the median, in percent, reported as a regression
+    #[clap(long, default_value = "10")]
+    threshold: f64,
+}
+
+#[derive(Args)]
+struct HistoryArgs {
+    /// Only show runs of this day
+    #[clap(short, long)]
+    day: Option<u8>,
+
+    /// Only show runs on this input
+    #[clap(short, long)]
+    input: Option<String>,
+
+    /// Number of most recent runs to show per day and input
+    #[clap(short = 'n', long, default_value = "10")]
+    limit: usize,
+}
+
+#[derive(Args)]
+struct TestArgs {
+    /// Rewrite the stored snapshots with the current rendering instead of comparing them
+    #[clap(long)]
+    update_snapshots: bool,
+}
+
+#[derive(Args)]
+struct SubmitArgs {
+    /// Day to submit
+    #[clap(short, long)]
+    day: u8,
+
+    /// Part to submit: 1 or 2
+    #[clap(short, long)]
+    part: Part,
+
+    #[clap(flatten)]
+    input: InputArgs,
+}
+
+#[derive(Args)]
+struct ExamplesArgs {
+    /// Day the description belongs to
+    #[clap(short, long)]
+    day: u8,
+
+    /// Saved HTML of the puzzle description
+    #[clap(long)]
+    html: PathBuf,
+
+    /// Name of the input file to write in `day_XX/input/`
+    #[clap(short, long, default_value = "test")]
+    name: String,
+
+    /// Overwrite an existing input file and its answers
+    #[clap(long)]
+    force: bool,
+}
+
+/// A command that failed, with the exit code of its first error; the errors are already logged.
+#[derive(Debug, PartialEq, Eq)]
+struct Failed(i32);
+
+type Status = std::result::Result<(), Failed>;
+
+fn main() {
+    let cli = Cli::parse();
+    let tracing = match cli.output.init() {
+        Ok(guard) => guard,
+        Err(error) => {
+            eprintln!("{}", error);
+            std::process::exit(error.exit_code());
+        }
+    };
+
+    let status = match cli.command {
+        Command::Run(args) => run(&args, cli.output.format),
+        Command::Fetch(days) => fetch(&days),
+        Command::Submit(args) => submit_answer(&args),
+        Command::Examples(args) => examples(&args),
+        Command::Verify => verify_answers(),
+        Command::Bench(args) => bench(&args),
+        Command::History(args) => history(&args),
+        Command::Test(args) => test(&args),
+    };
+
+    // Exiting skips destructors, so the trace has to be completed first.
+    drop(tracing);
+    if let Err(Failed(code)) = status {
+        std::process::exit(code);
+    }
+}
+
+/// Logs the error and picks the exit code matching its kind.
+fn report(error: AOCError) -> Failed {
+    error!("{}", error);
+    Failed(error.exit_code())
+}
+
+fn report_day(day: u8, error: AOCError) -> Failed {
+    error!("Day {}: {}", day, error);
+    Failed(error.exit_code())
+}
+
+fn selected_puzzles(selection: &DaySelection) -> Result<Vec<&'static dyn Puzzle>> {
+    match selection.day {
+        Some(day) => registry::puzzle_for(day)
+            .map(|puzzle| vec![puzzle])
+            .ok_or_else(|| {
+                AOCError::InvalidInput(format!("No solution registered for day {}", day))
+            }),
+        None => Ok(registry::DAYS.to_vec()),
+    }
+}
+
+/// Runs `action` for every selected day, failing with the first error once all days ran.
+fn for_each_puzzle<F>(selection: &DaySelection, mut action: F) -> Status
+where
+    F: FnMut(&dyn Puzzle) -> Status,
+{
+    let mut status = Ok(());
+    for puzzle in selected_puzzles(selection).map_err(report)? {
+        let _span = info_span!("day", day = puzzle.day()).entered();
+        info!("Day {}", puzzle.day());
+        let result = action(puzzle);
+        status = status.and(result);
+    }
+    status
+}
+
+fn run(args: &RunArgs, format: Format) -> Status {
+    let source = args.solve.input.source();
+    let input = source.to_string();
+    let mut recorder = if args.no_history {
+        None
+    } else {
+        Recorder::new(&source, args.slowdown)
+    };
+    // Stdin can be read only once, so it is buffered unless a single streaming part reads it.
+    let streamed_once = args.solve.part.level().is_some()
+        && args
+            .days
+            .day
+            .and_then(registry::puzzle_for)
+            .is_some_and(|puzzle| puzzle.streams());
+    let source = if streamed_once {
+        source
+    } else {
+        source.buffered().map_err(report)?
+    };
+    let mut reporter = Reporter::new(format);
+    let status = for_each_puzzle(&args.days, |puzzle| {
+        match run_day(puzzle, &source, args.solve.part) {
+            Ok(outcome) => {
+                let status = match outcome.first_error() {
+                    Some(error) => Err(Failed(error.exit_code())),
+                    None => Ok(()),
+                };
+                if let Some(recorder) = recorder.as_mut() {
+                    recorder.record(puzzle.day(), &input, &outcome);
+                }
+                reporter.outcome(puzzle.day(), &input, outcome);
+                status
+            }
+            Err(error) => {
+                reporter.failure(puzzle.day(), &input, args.solve.part, &error);
+                Err(report_day(puzzle.day(), error))
+            }
+        }
+    });
+    reporter.finish();
+    status
+}
+
+/// Appends runs to the history, warning about changed answers and slowdowns.
+struct Recorder {
+    history: History,
+    revision: Option<String>,
+    slowdown: f64,
+}
+
+impl Recorder {
+    /// Runs on stdin or literal text are not recorded, as their input can differ every time.
+    fn new(source: &InputSource, slowdown: f64) -> Option<Recorder> {
+        if matches!(source, InputSource::Stdin | InputSource::Literal(_)) {
+            return None;
+        }
+        match History::load(Path::new(HISTORY_FILE)) {
+            Ok(history) => Some(Recorder {
+                history,
+                revision: git_revision(),
+                slowdown,
+            }),
+            Err(error) => {
+                warn!("Not recording history: {}", error);
+                None
+            }
+        }
+    }
+
+    fn record(&mut self, day: u8, input: &str, outcome: &Outcome) {
+        let record = RunRecord::new(day, input, outcome, self.revision.clone(), now());
+        for warning in self.history.check(&record, self.slowdown) {
+            warn!("Day {} on {}: {}", day, input, warning);
+        }
+        if let Err(error) = self.history.append(record) {
+            warn!("Failed to record run: {}", error);
+        }
+    }
+}
+
+fn run_day(puzzle: &dyn Puzzle, source: &InputSource, part: Part) -> Result<Outcome> {
+    let day = day_dir(puzzle.day());
+    if *source == InputSource::Named(PUZZLE_INPUT.to_owned())
+        && !input_path(&day, PUZZLE_INPUT).exists()
+    {
+        fetch_day(puzzle)?;
+    }
+
+    solve_source(puzzle, source, part)
+}
+
+fn solve_source(puzzle: &dyn Puzzle, source: &InputSource, part: Part) -> Result<Outcome> {
+    let day = day_dir(puzzle.day());
+    puzzle
+        .solve_reader(&mut || source.open(&day), part)
+        .map_err(|err| match source.path(&day) {
+            Ok(Some(path)) => err.with_path(&path),
+            _ => err,
+        })
+}
+
+fn bench(args: &BenchArgs) -> Status {
+    let settings = BenchSettings {
+        warmup: args.warmup,
+        iterations: args.iterations,
+    };
+    let source = args.input.source().buffered().map_err(report)?;
+    let mut baseline = Baseline::load(&args.baseline).map_err(report)?;
+    let mut regressions = 0;
+    let status = for_each_puzzle(&args.days, |puzzle| {
+        let timings =
+            bench_day(puzzle, &source, settings).map_err(|err| report_day(puzzle.day(), err))?;
+        let day = day_dir(puzzle.day());
+        let input = source.to_string();
+        match baseline.get(&day, &input) {
+            Some(previous) => {
+                for ((phase, stats), (_, before)) in timings.phases().iter().zip(previous.phases())
+                {
+                    let change = relative_change(before.median, stats.median);
+                    info!("{:<8} {} ({:+.1}%)", phase, stats, change * 100.0);
+                }
+                for regression in timings.regressions(previous, args.threshold / 100.0) {
+                    warn!("Day {}: {}", puzzle.day(), regression);
+                    regressions += 1;
+                }
+            }
+            None => {
+                for (phase, stats) in timings.phases() {
+                    info!("{:<8} {}", phase, stats);
+                }
+            }
+        }
+        baseline.insert(&day, &input, timings);
+        Ok(())
+    });
+
+    if args.save {
+        baseline.save().map_err(report)?;
+        info!("Baseline saved to {}", args.baseline.display());
+    }
+    if regressions > 0 {
+        error!("{} phase(s) regressed against the baseline", regressions);
+        return Err(Failed(EXIT_FAILURE));
+    }
+    status
+}
+
+fn bench_day(
+    puzzle: &dyn Puzzle,
+    source: &InputSource,
+    settings: BenchSettings,
+) -> Result<Timings> {
+    let day = day_dir(puzzle.day());
+    let input = source.read(&day)?;
+    puzzle
+        .bench(&input, settings)
+        .map_err(|err| match source.path(&day) {
+            Ok(Some(path)) => err.with_path(&path),
+            _ => err,
+        })
+}
+
+fn history(args: &HistoryArgs) -> Status {
+    let history = History::load(Path::new(HISTORY_FILE)).map_err(report)?;
+
+    let mut runs: BTreeMap<(u8, &str), Vec<&RunRecord>> = BTreeMap::new();
+    for record in history.records() {
+        if args.day.is_none_or(|day| day == record.day)
+            && args
+                .input
+                .as_ref()
+                .is_none_or(|input| *input == record.input)
+        {
+            runs.entry((record.day, &record.input))
+                .or_default()
+                .push(record);
+        }
+    }
+    if runs.is_empty() {
+        info!("No runs recorded in {}", HISTORY_FILE);
+    }
+
+    for ((day, input), records) in runs {
+        println!("{} on {}", day_dir(day), input);
+        println!(
+            "{:<22} {:<16} {:>10} {:>10} {:>16} {:>10} {:>16}",
+            "time", "revision", "parse", "part one", "answer", "part two", "answer"
+        );
+        for record in records
+            .iter()
+            .skip(records.len().saturating_sub(args.limit))
+        {
+            let part = |part: Option<&PartRecord>| match part {
+                Some(part) => (
+                    display_duration(Duration::from_secs_f64(part.elapsed_ms / 1e3)),
+                    part.answer.clone().unwrap_or_else(|| "failed".to_owned()),
+                ),
+                None => (String::new(), String::new()),
+            };
+            let (one_time, one_answer) = part(record.part_one.as_ref());
+            let (two_time, two_answer) = part(record.part_two.as_ref());
+            println!(
+                "{:<22} {:<16} {:>10} {:>10} {:>16} {:>10} {:>16}",
+                format_timestamp(record.timestamp),
+                record.revision.as_deref().unwrap_or("-"),
+                display_duration(Duration::from_secs_f64(record.parse_ms / 1e3)),
+                one_time,
+                one_answer,
+                two_time,
+                two_answer
+            );
+        }
+
+        let medians: Vec<String> = ["one", "two"]
+            .iter()
+            .enumerate()
+            .filter_map(|(index, name)| {
+                let window: Vec<f64> = records
+                    .iter()
+                    .rev()
+                    .filter_map(|record| record.parts()[index].1)
+                    .take(ROLLING_WINDOW)
+                    .map(|part| part.elapsed_ms)
+                    .collect();
+                median(window).map(|ms| {
+                    format!(
+                        "part {} {}",
+                        name,
+                        display_duration(Duration::from_secs_f64(ms / 1e3))
+                    )
+                })
+            })
+            .collect();
+        println!(
+            "median of the last {} runs: {}\n",
+            ROLLING_WINDOW,
+            medians.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn test(args: &TestArgs) -> Status {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned());
+    let mut command = std::process::Command::new(cargo);
+    command.args(["test", "--workspace"]);
+    if args.update_snapshots {
+        command.env(UPDATE_SNAPSHOTS, "1");
+    }
+
+    match command.status() {
+        Ok(status) if status.success() => Ok(()),
+        Ok(status) => Err(Failed(status.code().unwrap_or(EXIT_FAILURE))),
+        Err(error) => {
+            error!("Failed to run cargo: {}", error);
+            Err(Failed(EXIT_IO))
+        }
+    }
+}
+
+fn fetch(days: &DaySelection) -> Status {
+    for_each_puzzle(days, |puzzle| {
+        fetch_day(puzzle).map_err(|err| report_day(puzzle.day(), err))
+    })
+}
+
+fn fetch_day(puzzle: &dyn Puzzle) -> Result<()> {
+    let path = input_path(&day_dir(puzzle.day()), PUZZLE_INPUT);
+    if path.exists() {
+        info!("Input already cached at {}", path.display());
+        return Ok(());
+    }
+    Client::from_env()?.ensure_input(puzzle.day(), &path)
+}
+
+fn submit_answer(args: &SubmitArgs) -> Status {
+    let verdict = compute_and_submit(args).map_err(|err| report_day(args.day, err))?;
+    info!("Verdict: {}", verdict);
+    if verdict == Verdict::Correct {
+        Ok(())
+    } else {
+        Err(Failed(EXIT_FAILURE))
+    }
+}
+
+fn compute_and_submit(args: &SubmitArgs) -> Result<Verdict> {
+    let puzzle = registry::puzzle_for(args.day).ok_or_else(|| {
+        AOCError::InvalidInput(format!("No solution registered for day {}", args.day))
+    })?;
+    let level = args
+        .part
+        .level()
+        .ok_or_else(|| AOCError::invalid_input("Only a single part can be submitted"))?;
+
+    let outcome = solve_source(puzzle, &args.input.source(), args.part)?;
+    let answer = outcome
+        .part_one
+        .or(outcome.part_two)
+        .ok_or_else(|| AOCError::no_solution("No answer computed"))??;
+
+    let mut log = SubmissionLog::load(Path::new(SUBMISSION_LOG))?;
+    submit(&Client::from_env()?, &mut log, args.day, level, &answer)
+}
+
+fn examples(args: &ExamplesArgs) -> Status {
+    let result = extract_example_from_file(&args.html).and_then(|example| {
+        info!("Extracted example:\n{}", example);
+        let mut answers = ExpectedAnswers::load(Path::new(ANSWERS_FILE))?;
+        write_example(
+            &example,
+            &day_dir(args.day),
+            &args.name,
+            &mut answers,
+            args.force,
+        )
+    });
+
+    result.map_err(|err| report_day(args.day, err))
+}
+
+fn verify_answers() -> Status {
+    let answers = ExpectedAnswers::load(Path::new(ANSWERS_FILE)).map_err(report)?;
+
+    let unregistered: Vec<&String> = answers
+        .days()
+        .filter(|day| !registry::DAYS.iter().any(|p| day_dir(p.day()) == **day))
+        .collect();
+    for day in &unregistered {
+        error!("Answers listed for {}, but no solution is registered", day);
+    }
+
+    println!(
+        "{:<8} {:<10} {:<40} {:<40}",
+        "day", "input", "part one", "part two"
+    );
+    let failures = registry::DAYS
+        .iter()
+        .flat_map(|puzzle| {
+            let day = day_dir(puzzle.day());
+            answers
+                .inputs_for(&day)
+                .map(|(input, expected)| {
+                    let _span = info_span!("day", day = puzzle.day(), input).entered();
+                    let (one, two) = verify(expected, solve_input(*puzzle, input));
+                    println!(
+                        "{:<8} {:<10} {:<40} {:<40}",
+                        day,
+                        input,
+                        one.to_string(),
+                        two.to_string()
+                    );
+                    one.is_failure() || two.is_failure()
+                })
+                .collect::<Vec<bool>>()
+        })
+        .filter(|&failed| failed)
+        .count();
+
+    if failures > 0 {
+        error!("{} input(s) did not match the expected answers", failures);
+    }
+    if failures == 0 && unregistered.is_empty() {
+        Ok(())
+    } else {
+        Err(Failed(EXIT_FAILURE))
+    }
+}
+
+fn solve_input(puzzle: &dyn Puzzle, input: &str) -> Outcome {
+    let outcome = solve_source(puzzle, &InputSource::Named(input.to_owned()), Part::Both);
+
+    outcome.unwrap_or_else(|error| {
+        let message = error.to_string();
+        Outcome {
+            part_one: Some(Err(AOCError::new(message.clone()))),
+            part_two: Some(Err(AOCError::new(message))),
+            elapsed: Elapsed::default(),
+        }
+    })
+}
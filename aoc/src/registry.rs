@@ -0,0 +1,18 @@
+use utils::solution::Puzzle;
+
+pub const DAYS: [&dyn Puzzle; 10] = [
+    &day_01::Day01,
+    &day_02::Day02,
+    &day_03::Day03,
+    &day_04::Day04,
+    &day_05::Day05,
+    &day_06::Day06,
+    &day_07::Day07,
+    &day_08::Day08,
+    &day_09::Day09,
+    &day_10::Day10,
+];
+
+pub fn puzzle_for(day: u8) -> Option<&'static dyn Puzzle> {
+    DAYS.iter().find(|puzzle| puzzle.day() == day).copied()
+}
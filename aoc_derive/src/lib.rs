@@ -0,0 +1,277 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::format_ident;
+use quote::quote;
+use syn::parse_macro_input;
+use syn::spanned::Spanned;
+use syn::Attribute;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Lit;
+use syn::Meta;
+use syn::NestedMeta;
+
+/// Derives `FromStr` from a pattern such as `#[aoc(pattern = "{start} -> {end}")]`.
+///
+/// Every `{field}` is parsed with the field's own `FromStr`, `{}` takes the next field in
+/// declaration order. Fields marked `#[aoc(separator = " ")]` are parsed as a list of values.
+/// Whitespace around a field's text is ignored.
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(Option<String>),
+}
+
+struct Field {
+    member: TokenStream2,
+    name: String,
+    separator: Option<String>,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let pattern = attribute_value(&input.attrs, "pattern")?
+        .ok_or_else(|| Error::new(input.span(), "missing #[aoc(pattern = \"...\")]"))?;
+    let segments = parse_pattern(&pattern).map_err(|message| Error::new(input.span(), message))?;
+
+    let data = match &input.data {
+        Data::Struct(data) => data,
+        _ => return Err(Error::new(input.span(), "AocParse only supports structs")),
+    };
+    let fields: Vec<Field> = data
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let (member, name) = match &field.ident {
+                Some(ident) => (quote!(#ident), ident.to_string()),
+                None => {
+                    let index = syn::Index::from(index);
+                    (quote!(#index), index.index.to_string())
+                }
+            };
+            Ok(Field {
+                member,
+                name,
+                separator: attribute_value(&field.attrs, "separator")?,
+            })
+        })
+        .collect::<syn::Result<_>>()?;
+
+    let mut next_field = 0;
+    let mut used = vec![false; fields.len()];
+    let mut steps = Vec::new();
+    for (index, segment) in segments.iter().enumerate() {
+        match segment {
+            Segment::Literal(literal) if index == 0 => steps.push(quote! {
+                let rest = rest.strip_prefix(#literal).ok_or_else(|| {
+                    ::utils::results::AOCError::parse(rest, concat!("expected '", #literal, "'"))
+                })?;
+            }),
+            Segment::Literal(_) => {}
+            Segment::Field(name) => {
+                let position = match name {
+                    Some(name) => fields.iter().position(|field| &field.name == name),
+                    None => Some(next_field).filter(|&position| position < fields.len()),
+                }
+                .ok_or_else(|| {
+                    Error::new(
+                        input.span(),
+                        format!(
+                            "pattern field {{{}}} does not exist",
+                            name.as_deref().unwrap_or("")
+                        ),
+                    )
+                })?;
+                if used[position] {
+                    return Err(Error::new(
+                        input.span(),
+                        format!(
+                            "field {} appears twice in the pattern",
+                            fields[position].name
+                        ),
+                    ));
+                }
+                used[position] = true;
+                next_field = position + 1;
+
+                let variable = format_ident!("field_{}", position);
+                let split = match segments.get(index + 1) {
+                    Some(Segment::Literal(literal)) => quote! {
+                        let (raw, rest) = rest.split_once(#literal).ok_or_else(|| {
+                            ::utils::results::AOCError::parse(rest, concat!("expected '", #literal, "'"))
+                        })?;
+                    },
+                    _ => quote! {
+                        let (raw, rest) = (rest, "");
+                    },
+                };
+                let value = field_parser(&fields[position]);
+                steps.push(quote! {
+                    #split
+                    let raw = raw.trim();
+                    let #variable = #value;
+                });
+            }
+        }
+    }
+
+    if let Some(position) = used.iter().position(|used| !used) {
+        return Err(Error::new(
+            input.span(),
+            format!(
+                "field {} is missing from the pattern",
+                fields[position].name
+            ),
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let construct = fields.iter().enumerate().map(|(position, field)| {
+        let member = &field.member;
+        let variable = format_ident!("field_{}", position);
+        quote!(#member: #variable)
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::std::str::FromStr for #name #type_generics #where_clause {
+            type Err = ::utils::results::AOCError;
+
+            fn from_str(input: &str) -> ::std::result::Result<Self, <Self as ::std::str::FromStr>::Err> {
+                let rest = input;
+                #(#steps)*
+                if !rest.is_empty() {
+                    return Err(::utils::results::AOCError::parse(rest, "unexpected trailing input"));
+                }
+                Ok(#name { #(#construct),* })
+            }
+        }
+    })
+}
+
+fn field_parser(field: &Field) -> TokenStream2 {
+    let parse_item = quote! {
+        |item: &str| item
+            .parse()
+            .map_err(|err| ::utils::results::AOCError::from_parse_error(item, err))
+    };
+    match field.separator.as_deref() {
+        None => quote! { (#parse_item)(raw)? },
+        Some(separator) if separator.trim().is_empty() => quote! {
+            raw.split_whitespace()
+                .map(#parse_item)
+                .collect::<::utils::results::Result<_>>()?
+        },
+        Some(separator) => quote! {
+            raw.split(#separator)
+                .map(#parse_item)
+                .collect::<::utils::results::Result<_>>()?
+        },
+    }
+}
+
+fn attribute_value(attrs: &[Attribute], key: &str) -> syn::Result<Option<String>> {
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("aoc")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            other => return Err(Error::new(other.span(), "expected #[aoc(key = \"value\")]")),
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(pair)) if pair.path.is_ident(key) => {
+                    return match pair.lit {
+                        Lit::Str(value) => Ok(Some(value.value())),
+                        Lit::Char(value) => Ok(Some(value.value().to_string())),
+                        other => Err(Error::new(other.span(), "expected a string")),
+                    };
+                }
+                NestedMeta::Meta(Meta::NameValue(pair))
+                    if pair.path.is_ident("pattern") || pair.path.is_ident("separator") => {}
+                other => return Err(Error::new(other.span(), "unknown aoc attribute")),
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Splits a pattern into literals and `{field}` placeholders; `{{` and `}}` are literal braces.
+fn parse_pattern(pattern: &str) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err("unclosed '{' in pattern".to_owned()),
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                } else if matches!(segments.last(), Some(Segment::Field(_))) {
+                    return Err("placeholders must be separated by a literal".to_owned());
+                }
+                let name = name.trim();
+                segments.push(Segment::Field(
+                    Some(name.to_owned()).filter(|n| !n.is_empty()),
+                ));
+            }
+            '}' => return Err("unmatched '}' in pattern".to_owned()),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn pattern_is_split() {
+        assert_eq!(
+            parse_pattern("{start} -> {end}"),
+            Ok(vec![
+                Segment::Field(Some("start".to_owned())),
+                Segment::Literal(" -> ".to_owned()),
+                Segment::Field(Some("end".to_owned())),
+            ])
+        );
+        assert_eq!(
+            parse_pattern("<{}>{{x}}"),
+            Ok(vec![
+                Segment::Literal("<".to_owned()),
+                Segment::Field(None),
+                Segment::Literal(">{x}".to_owned()),
+            ])
+        );
+        assert!(parse_pattern("{a}{b}").is_err());
+        assert!(parse_pattern("a}").is_err());
+        assert!(parse_pattern("{a").is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use itertools::process_results;
+use itertools::Itertools;
+use std::io::BufRead;
+use utils::aoc_tests;
+use utils::input::*;
+use utils::results::*;
+use utils::solution::*;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+
+    type Input = Vec<i32>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part_one(depths: &Self::Input) -> Result<Self::PartOne> {
+        part_one(depths)
+    }
+
+    fn part_two(depths: &Self::Input) -> Result<Self::PartTwo> {
+        part_two(depths)
+    }
+
+    const STREAMING: bool = true;
+
+    fn part_one_from_reader(reader: &mut dyn BufRead) -> Result<Self::PartOne> {
+        part_one_from_reader(reader)
+    }
+
+    fn part_two_from_reader(reader: &mut dyn BufRead) -> Result<Self::PartTwo> {
+        part_two_from_reader(reader)
+    }
+}
+
+fn part_one(depths: &[i32]) -> Result<usize> {
+    Ok(count_increases(depths.iter().copied()))
+}
+
+fn part_two(depths: &[i32]) -> Result<usize> {
+    Ok(count_increases(window_sums(depths.iter().copied())))
+}
+
+fn part_one_from_reader<R: BufRead>(reader: R) -> Result<usize> {
+    process_results(parse_lines_iter(reader), |depths| count_increases(depths))
+}
+
+fn part_two_from_reader<R: BufRead>(reader: R) -> Result<usize> {
+    process_results(parse_lines_iter(reader), |depths| {
+        count_increases(window_sums(depths))
+    })
+}
+
+fn count_increases<I: Iterator<Item = i32>>(depths: I) -> usize {
+    depths.tuple_windows().filter(|(a, b)| a < b).count()
+}
+
+fn window_sums<I: Iterator<Item = i32>>(depths: I) -> impl Iterator<Item = i32> {
+    depths.tuple_windows().map(|(a, b, c)| a + b + c)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn part_one_is_correct() {
+        let depths = vec![1, 2, 3, 2, 1];
+        assert_eq!(part_one(&depths), Ok(2));
+
+        let depths = vec![9, 8, 7, 6];
+        assert_eq!(part_one(&depths), Ok(0));
+
+        let depths = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(part_one(&depths), Ok(7));
+    }
+
+    #[test]
+    fn part_two_is_correct() {
+        let depths = vec![1, 2, 3];
+        assert_eq!(part_two(&depths), Ok(0));
+
+        let depths = vec![1, 2, 3, 4];
+        assert_eq!(part_two(&depths), Ok(1));
+
+        let depths = vec![1, 2, 3, 1];
+        assert_eq!(part_two(&depths), Ok(0));
+
+        let depths = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(part_two(&depths), Ok(5));
+    }
+
+    #[test]
+    fn stream_is_solved() {
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        assert_eq!(part_one_from_reader(input.as_bytes()), Ok(7));
+        assert_eq!(part_two_from_reader(input.as_bytes()), Ok(5));
+
+        assert!(part_one_from_reader("199\n2x0\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn puzzle_reopens_the_stream_per_part() {
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        let mut opened = 0;
+        let outcome = Day01
+            .solve_reader(
+                &mut || {
+                    opened += 1;
+                    Ok(Box::new(input.as_bytes()))
+                },
+                Part::Both,
+            )
+            .unwrap();
+
+        assert_eq!(opened, 2);
+        assert_eq!(outcome.part_one, Some(Ok("7".to_owned())));
+        assert_eq!(outcome.part_two, Some(Ok("5".to_owned())));
+    }
+}
+
+aoc_tests!(
+    Day01,
+    test => (7, 5),
+    puzzle => (1521, 1543),
+);
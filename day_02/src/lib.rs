@@ -1,29 +1,31 @@
-use itertools::Itertools;
+use aoc_derive::AocParse;
 use std::fmt::Display;
 use std::str::FromStr;
+use utils::aoc_tests;
 use utils::input::*;
-use utils::output::*;
 use utils::results::*;
+use utils::solution::*;
 
-#[macro_use]
-extern crate log;
-
-fn main() {
-    init_env_log();
-
-    let day = parse_day(file!());
-    let file = file_name_from_args();
-    let instructions: Vec<Instruction> =
-        parse_lines_from_file(&path_for_day(day, &file).unwrap()).unwrap();
-
-    info!(
-        "Solution to part one: {}",
-        display_result(part_one(&instructions))
-    );
-    info!(
-        "Solution to part two: {}",
-        display_result(part_two(&instructions))
-    );
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+
+    type Input = Vec<Instruction>;
+    type PartOne = i64;
+    type PartTwo = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part_one(instructions: &Self::Input) -> Result<Self::PartOne> {
+        part_one(instructions)
+    }
+
+    fn part_two(instructions: &Self::Input) -> Result<Self::PartTwo> {
+        part_two(instructions)
+    }
 }
 
 fn part_one(instructions: &[Instruction]) -> Result<i64> {
@@ -122,12 +124,14 @@ impl Display for PositionAndAim {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct Instruction {
+#[derive(Debug, Clone, PartialEq, AocParse)]
+#[aoc(pattern = "{direction} {steps}")]
+pub struct Instruction {
     direction: Direction,
     steps: u32,
 }
 
+#[cfg(test)]
 impl Instruction {
     fn new(direction: Direction, steps: u32) -> Instruction {
         Instruction { direction, steps }
@@ -166,24 +170,7 @@ impl FromStr for Direction {
             "down" => Ok(Direction::Down),
             "up" => Ok(Direction::Up),
             "forward" => Ok(Direction::Forward),
-            unrec => Err(AOCError::new(format!(
-                "Failed to parse '{}' as direction",
-                unrec
-            ))),
-        }
-    }
-}
-
-impl FromStr for Instruction {
-    type Err = AOCError;
-
-    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
-        if let Some((direction_input, steps_input)) = input.split_whitespace().collect_tuple() {
-            let direction = direction_input.parse()?;
-            let steps = steps_input.parse::<u32>()?;
-            Ok(Instruction::new(direction, steps))
-        } else {
-            Err(AOCError::new(format!("Could not parse '{}'", input)))
+            unrec => Err(AOCError::parse(unrec, "not a direction")),
         }
     }
 }
@@ -270,4 +257,38 @@ mod test {
             Ok(Instruction::new(Direction::Up, 3))
         );
     }
+
+    #[test]
+    fn parse_instruction_with_extra_whitespace() {
+        assert_eq!(
+            "forward 5 ".parse::<Instruction>(),
+            Ok(Instruction::new(Direction::Forward, 5))
+        );
+        assert_eq!(
+            "up  3".parse::<Instruction>(),
+            Ok(Instruction::new(Direction::Up, 3))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_instruction() {
+        assert!(matches!(
+            "sideways 3".parse::<Instruction>(),
+            Err(AOCError::Parse { input, .. }) if input == "sideways"
+        ));
+        assert!(matches!(
+            "up three".parse::<Instruction>(),
+            Err(AOCError::Parse { input, .. }) if input == "three"
+        ));
+        assert!(matches!(
+            "up".parse::<Instruction>(),
+            Err(AOCError::Parse { .. })
+        ));
+    }
 }
+
+aoc_tests!(
+    Day02,
+    test => (150, 900),
+    puzzle => (1868935, 1965970888),
+);
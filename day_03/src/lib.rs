@@ -0,0 +1,237 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use tracing::debug;
+use tracing::debug_span;
+use tracing::instrument;
+use utils::aoc_tests;
+use utils::input::*;
+use utils::results::*;
+use utils::solution::*;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+
+    type Input = Vec<BinaryNumber>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    /// Every line must have as many bits as the first one.
+    fn parse(input: &str) -> Result<Self::Input> {
+        let numbers: Vec<BinaryNumber> = parse_lines(input)?;
+        if let Some(first) = numbers.first() {
+            if let Some((index, number)) = numbers
+                .iter()
+                .enumerate()
+                .find(|(_, number)| number.len() != first.len())
+            {
+                return Err(AOCError::InvalidInput(format!(
+                    "Line {} has {} bits, but line 1 has {}",
+                    index + 1,
+                    number.len(),
+                    first.len()
+                )));
+            }
+        }
+        Ok(numbers)
+    }
+
+    fn part_one(numbers: &Self::Input) -> Result<Self::PartOne> {
+        part_one(numbers)
+    }
+
+    fn part_two(numbers: &Self::Input) -> Result<Self::PartTwo> {
+        part_two(numbers)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BinaryNumber(Vec<bool>);
+
+impl BinaryNumber {
+    fn new(vec: Vec<bool>) -> BinaryNumber {
+        BinaryNumber(vec)
+    }
+
+    fn bit_at(&self, index: usize) -> bool {
+        self.0[index]
+    }
+
+    fn counts(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.0.iter().map(|&b| if b { (1, 0) } else { (0, 1) })
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl Display for BinaryNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(|&b| if b { '1' } else { '0' })
+                .collect::<String>()
+        )
+    }
+}
+
+impl From<&BinaryNumber> for u64 {
+    fn from(number: &BinaryNumber) -> Self {
+        number
+            .0
+            .iter()
+            .fold(0, |sum, &bit| (sum << 1) + if bit { 1 } else { 0 })
+    }
+}
+
+impl FromStr for BinaryNumber {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        if input.len() > u64::BITS as usize {
+            return Err(AOCError::parse(input, "expected at most 64 bits"));
+        }
+        input
+            .chars()
+            .map(|c| match c {
+                '1' => Ok(true),
+                '0' => Ok(false),
+                _ => Err(AOCError::parse(input, "expected '0' or '1'")),
+            })
+            .collect::<Result<Vec<bool>>>()
+            .map(BinaryNumber::new)
+    }
+}
+
+fn part_one(numbers: &[BinaryNumber]) -> Result<u64> {
+    let mut iterator = numbers.iter();
+
+    iterator
+        .next()
+        .map(|head| {
+            let counts: Vec<(usize, usize)> = iterator.fold(head.counts().collect(), {
+                |counts, number| {
+                    counts
+                        .iter()
+                        .zip(number.counts())
+                        .map(|((a, b), (c, d))| (a + c, b + d))
+                        .collect()
+                }
+            });
+
+            let gamma =
+                BinaryNumber::new(counts.iter().map(|(ones, zeros)| ones > zeros).collect());
+            debug!(%gamma, value = u64::from(&gamma), "gamma rate");
+            let epsilon =
+                BinaryNumber::new(counts.iter().map(|(ones, zeros)| ones < zeros).collect());
+            debug!(%epsilon, value = u64::from(&epsilon), "epsilon rate");
+
+            power_product(&gamma, &epsilon)
+        })
+        .ok_or_else(|| AOCError::invalid_input("Empty list of numbers"))?
+}
+
+fn part_two(numbers: &[BinaryNumber]) -> Result<u64> {
+    let oxygen_generator_rating = rating(numbers, "oxygen generator", true)?;
+    let co2_scrubber_rating = rating(numbers, "CO2 scrubber", false)?;
+
+    power_product(oxygen_generator_rating, co2_scrubber_rating)
+}
+
+fn power_product(first: &BinaryNumber, second: &BinaryNumber) -> Result<u64> {
+    u64::from(first)
+        .checked_mul(u64::from(second))
+        .ok_or_else(|| {
+            AOCError::InvalidInput(format!("The product of {} and {} overflows", first, second))
+        })
+}
+
+fn rating<'a>(
+    numbers: &'a [BinaryNumber],
+    name: &str,
+    keep_largest: bool,
+) -> Result<&'a BinaryNumber> {
+    let _span = debug_span!("rating", name).entered();
+    let rating = filter_by_bit(numbers.iter().collect(), 0, keep_largest)?;
+    debug!(%rating, value = u64::from(rating), "found rating");
+    Ok(rating)
+}
+
+#[allow(clippy::collapsible_else_if)]
+#[instrument(level = "trace", skip(list), fields(candidates = list.len()))]
+fn filter_by_bit(
+    list: Vec<&BinaryNumber>,
+    index: usize,
+    keep_largest: bool,
+) -> Result<&BinaryNumber> {
+    if list.is_empty() {
+        Err(AOCError::no_solution("No number found"))
+    } else if list.len() == 1 {
+        Ok(list[0])
+    } else if list[0].len() <= index {
+        Err(AOCError::InvalidInput(format!(
+            "Trying to partition by index {} on lists of length {}",
+            index,
+            list[0].len()
+        )))
+    } else {
+        let (ones, zeros): (Vec<&BinaryNumber>, Vec<&BinaryNumber>) =
+            list.iter().cloned().partition(|n| n.bit_at(index));
+
+        if ones.len() >= zeros.len() {
+            if keep_largest {
+                filter_by_bit(ones, index + 1, keep_largest)
+            } else {
+                filter_by_bit(zeros, index + 1, keep_largest)
+            }
+        } else {
+            if keep_largest {
+                filter_by_bit(zeros, index + 1, keep_largest)
+            } else {
+                filter_by_bit(ones, index + 1, keep_largest)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn malformed_numbers_are_rejected() {
+        assert_eq!(
+            "1x1".parse::<BinaryNumber>().unwrap_err(),
+            AOCError::parse("1x1", "expected '0' or '1'")
+        );
+        assert!("1".repeat(65).parse::<BinaryNumber>().is_err());
+        assert_eq!(
+            Day03::parse("101\n10\n11\n").unwrap_err(),
+            AOCError::invalid_input("Line 2 has 2 bits, but line 1 has 3")
+        );
+    }
+
+    #[test]
+    fn overflowing_products_are_rejected() {
+        let input = format!(
+            "1{zeros}0\n1{zeros}1\n0{ones}1\n",
+            zeros = "0".repeat(62),
+            ones = "1".repeat(62)
+        );
+        let numbers = Day03::parse(&input).unwrap();
+        assert!(matches!(part_one(&numbers), Err(AOCError::InvalidInput(_))));
+        assert!(matches!(part_two(&numbers), Err(AOCError::InvalidInput(_))));
+    }
+}
+
+aoc_tests!(
+    Day03,
+    test => (198, 230),
+    puzzle => (1540244, 4203981),
+);
@@ -0,0 +1,258 @@
+use ansi_term::Color::Blue;
+use ansi_term::Color::Red;
+use ansi_term::Style;
+use either::{Either, Left, Right};
+use itertools::FoldWhile;
+use itertools::FoldWhile::{Continue, Done};
+use itertools::Itertools;
+use std::fmt::Display;
+use std::str::FromStr;
+use tracing::debug;
+use tracing::debug_span;
+use tracing::trace;
+use utils::aoc_tests;
+use utils::grid::*;
+use utils::input::*;
+use utils::output::paint;
+use utils::parser::*;
+use utils::results::*;
+use utils::solution::*;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+
+    type Input = (Vec<u8>, Vec<Board>);
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part_one((numbers, boards): &Self::Input) -> Result<Self::PartOne> {
+        part_one(numbers, boards)
+    }
+
+    fn part_two((numbers, boards): &Self::Input) -> Result<Self::PartTwo> {
+        part_two(numbers, boards)
+    }
+}
+
+fn part_one(numbers: &[u8], boards: &[Board]) -> Result<u64> {
+    let result = numbers
+        .iter()
+        .fold_while(Left(Vec::from(boards)), |boards, number| {
+            let _span = debug_span!("draw", number).entered();
+            let updated_boars: Vec<Board> = boards
+                .unwrap_left()
+                .iter()
+                .map(|b| b.select(number))
+                .collect();
+            updated_boars.iter().find(|b| b.is_winner()).map_or_else(
+                || Continue(Left(updated_boars.clone())),
+                |winner| Done(Right(winner.unmarked_sum() * *number as u64)),
+            )
+        });
+
+    match result {
+        Done(Right(score)) => Ok(score),
+        Continue(_) => Err(AOCError::no_solution("No winner found")),
+        Done(_) => Err(AOCError::new_from_ref("Finished without a winning board")),
+    }
+}
+
+fn part_two(numbers: &[u8], boards: &[Board]) -> Result<u64> {
+    let result: FoldWhile<Either<Vec<Board>, (Board, u8)>> =
+        numbers
+            .iter()
+            .fold_while(Left(Vec::from(boards)), |boards, number| {
+                let _span = debug_span!("draw", number).entered();
+                match boards {
+                    Left(multiple_boards) => {
+                        let updated_boards: Vec<Board> = multiple_boards
+                            .iter()
+                            .filter_map(|board| {
+                                Some(board.select(number)).filter(|b| !b.is_winner())
+                            })
+                            .collect();
+                        debug!(remaining = updated_boards.len(), "boards left");
+                        for board in &updated_boards {
+                            trace!("\n{}", board);
+                        }
+
+                        if updated_boards.len() == 1 {
+                            Continue(Right((updated_boards[0].clone(), *number)))
+                        } else {
+                            Continue(Left(updated_boards))
+                        }
+                    }
+                    Right((board, _)) => {
+                        let updated_board = board.select(number);
+                        let won = updated_board.is_winner();
+                        debug!(won, "last board");
+                        trace!("\n{}", updated_board);
+                        if won {
+                            Done(Right((updated_board, *number)))
+                        } else {
+                            Continue(Right((updated_board, *number)))
+                        }
+                    }
+                }
+            });
+
+    match result {
+        Done(result) => result
+            .map_right(|(board, number)| Ok(board.unmarked_sum() * number as u64))
+            .right_or(Err(AOCError::no_solution(
+                "Last board didn't win in the end",
+            ))),
+        Continue(_) => Err(AOCError::no_solution(
+            "No single board left at end of sequence",
+        )),
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Board {
+    numbers: Grid<u8>,
+    hits: Grid<bool>,
+}
+
+impl Board {
+    fn new(numbers: Vec<Vec<u8>>) -> Result<Board> {
+        let numbers = Grid::new(numbers)?;
+        let hits = Grid::filled(numbers.width(), numbers.height(), false)?;
+        Ok(Board { numbers, hits })
+    }
+
+    fn select(&self, number: &u8) -> Board {
+        match self.numbers.iter().find(|(_, n)| *n == number) {
+            None => self.clone(),
+            Some(((x, y), _)) => {
+                let mut hits = self.hits.clone();
+                if let Some(hit) = hits.get_mut(x, y) {
+                    *hit = true;
+                }
+                Board {
+                    numbers: self.numbers.clone(),
+                    hits,
+                }
+            }
+        }
+    }
+
+    fn is_winner(&self) -> bool {
+        self.hits.rows().any(|row| row.iter().all(|&hit| hit))
+            || self.hits.columns().any(|mut column| column.all(|&hit| hit))
+    }
+
+    fn unmarked_sum(&self) -> u64 {
+        self.numbers
+            .iter()
+            .filter(|&((x, y), _)| self.hits.get(x, y) == Some(&false))
+            .map(|(_, &number)| number as u64)
+            .sum()
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        for (numbers, hits) in self.numbers.rows().zip(self.hits.rows()) {
+            let row = numbers
+                .iter()
+                .zip(hits)
+                .map(|(number, &hit)| {
+                    let style = if hit {
+                        Style::new().bold().fg(Blue)
+                    } else {
+                        Style::new().fg(Red)
+                    };
+                    paint(style, format!("{:2}", number))
+                })
+                .join(" ");
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Vec<u8>, Vec<Board>)> {
+    let mut sections = sections(input);
+    let numbers = sections
+        .next()
+        .ok_or_else(|| AOCError::invalid_input("No numbers drawn"))?
+        .parse_with(|text| parse_delimited(text, ','))?;
+    let boards = sections
+        .map(|section| section.parse::<Board>())
+        .try_collect()?;
+
+    Ok((numbers, boards))
+}
+
+/// Lines of numbers separated by spaces, which may also pad the start and end of a line.
+fn rows<'a>() -> impl Parser<'a, Vec<Vec<u8>>> {
+    let row = delimited(spaces(), separated_list(number::<u8>(), spaces()), spaces());
+    separated_list(row, line_ending())
+}
+
+impl FromStr for Board {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        parse_all(rows(), input).and_then(Board::new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use utils::assert_snapshot;
+    use utils::output::set_color;
+    use utils::snapshot::Ansi;
+
+    #[test]
+    fn input_is_parsed() {
+        let (numbers, boards) = parse_input("7,4,9\n\n 1  2\n 3  4\n\n5 6\n7 8\n").unwrap();
+
+        assert_eq!(numbers, vec![7, 4, 9]);
+        assert_eq!(boards.len(), 2);
+        assert_eq!(boards[1].unmarked_sum(), 26);
+    }
+
+    #[test]
+    fn invalid_board_is_located() {
+        let error = parse_input("7,4,9\n\n1 2\n3 4\n\n5 6\n7 x8\n").unwrap_err();
+
+        match error {
+            AOCError::Section { number, error } => {
+                assert_eq!(number, 3);
+                assert!(error.to_string().contains("--> <input>:7:3"));
+            }
+            other => panic!("Unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn board_is_rendered() {
+        let (numbers, boards) = parse_input(
+            "7,4,9,5,11\n\n22 13 17 11  0\n 8  2 23  4 24\n21  9 14 16  7\n 6 10  3 18  5\n 1 12 20 15 19\n",
+        )
+        .unwrap();
+        let board = numbers
+            .iter()
+            .fold(boards[0].clone(), |board, number| board.select(number));
+
+        set_color(true);
+        assert_snapshot!("board", board);
+        assert_snapshot!("board_layout", board, Ansi::Strip);
+    }
+}
+
+aoc_tests!(
+    Day04,
+    test => (4512, 1924),
+    puzzle => (63552, 9020),
+);
@@ -0,0 +1,223 @@
+use aoc_derive::AocParse;
+use std::collections::HashMap;
+use std::fmt::Display;
+use utils::aoc_tests;
+use utils::input::*;
+use utils::results::*;
+use utils::solution::*;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+
+    type Input = Vec<Line>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part_one(lines: &Self::Input) -> Result<Self::PartOne> {
+        part_one(lines)
+    }
+
+    fn part_two(lines: &Self::Input) -> Result<Self::PartTwo> {
+        part_two(lines)
+    }
+}
+
+fn part_one(lines: &[Line]) -> Result<usize> {
+    let lines: Vec<&Line> = lines
+        .iter()
+        .filter(|l| l.is_horizontal() || l.is_vertical())
+        .collect();
+    Ok(Map::new(&lines).get_overlap_count())
+}
+
+fn part_two(lines: &[Line]) -> Result<usize> {
+    let lines: Vec<&Line> = lines.iter().collect();
+    Ok(Map::new(&lines).get_overlap_count())
+}
+
+/// The number of lines covering each point; only covered points are stored, as the
+/// coordinates can be far apart.
+#[derive(Debug)]
+struct Map {
+    point_count: HashMap<Point, usize>,
+}
+
+impl Map {
+    fn new(lines: &[&Line]) -> Map {
+        let mut point_count = HashMap::new();
+        for point in lines.iter().flat_map(|line| line.points()) {
+            *point_count.entry(point).or_insert(0) += 1;
+        }
+        Map { point_count }
+    }
+
+    fn get_count(&self, point: &Point) -> usize {
+        self.point_count.get(point).copied().unwrap_or(0)
+    }
+
+    fn get_overlap_count(&self) -> usize {
+        self.point_count.values().filter(|&&c| c > 1).count()
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        let points = || self.point_count.keys();
+        let xs = points().map(|p| p.x).min().zip(points().map(|p| p.x).max());
+        let ys = points().map(|p| p.y).min().zip(points().map(|p| p.y).max());
+        if let (Some((min_x, max_x)), Some((min_y, max_y))) = (xs, ys) {
+            for y in min_y..=max_y {
+                for x in min_x..=max_x {
+                    match self.get_count(&Point::new(x, y)) {
+                        0 => write!(f, ".")?,
+                        count => write!(f, "{}", count)?,
+                    }
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, AocParse)]
+#[aoc(pattern = "{x},{y}")]
+pub struct Point {
+    x: usize,
+    y: usize,
+}
+
+impl Point {
+    fn new(x: usize, y: usize) -> Point {
+        Point { x, y }
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+#[derive(Debug, AocParse)]
+#[aoc(pattern = "{start} -> {end}")]
+pub struct Line {
+    start: Point,
+    end: Point,
+}
+
+impl Line {
+    fn is_horizontal(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    fn is_vertical(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    fn points(&self) -> PointIterator {
+        PointIterator {
+            start: self.start,
+            end: self.end,
+            index: 0,
+        }
+    }
+}
+
+impl IntoIterator for Line {
+    type Item = Point;
+
+    type IntoIter = PointIterator;
+
+    fn into_iter(self) -> <Self as std::iter::IntoIterator>::IntoIter {
+        self.points()
+    }
+}
+
+pub struct PointIterator {
+    start: Point,
+    end: Point,
+    index: usize,
+}
+
+impl Iterator for PointIterator {
+    type Item = Point;
+
+    fn next(&mut self) -> std::option::Option<<Self as std::iter::Iterator>::Item> {
+        let dx: i64 = self.end.x as i64 - self.start.x as i64;
+        let dy: i64 = self.end.y as i64 - self.start.y as i64;
+
+        if (dx.unsigned_abs() as usize) >= self.index || (dy.unsigned_abs() as usize) >= self.index
+        {
+            let result = Point::new(
+                (self.start.x as i64 + (dx.signum() * self.index as i64)) as usize,
+                (self.start.y as i64 + (dy.signum() * self.index as i64)) as usize,
+            );
+            self.index += 1;
+            Some(result)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{} -> {}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use utils::assert_snapshot;
+
+    #[test]
+    fn line_is_parsed() {
+        let line: Line = "0,9 -> 5,9".parse().unwrap();
+        assert_eq!((line.start, line.end), (Point::new(0, 9), Point::new(5, 9)));
+        assert_eq!(line.to_string(), "0,9 -> 5,9");
+    }
+
+    #[test]
+    fn invalid_line_is_reported() {
+        assert!(matches!(
+            "0,9 => 5,9".parse::<Line>(),
+            Err(AOCError::Parse { input, .. }) if input == "0,9 => 5,9"
+        ));
+        assert!(matches!(
+            "0,9 -> 5;9".parse::<Line>(),
+            Err(AOCError::Parse { input, reason, .. }) if input == "5;9" && reason == "expected ','"
+        ));
+        assert!(matches!(
+            "0,x9 -> 5,9".parse::<Line>(),
+            Err(AOCError::Parse { input, .. }) if input == "x9"
+        ));
+    }
+
+    #[test]
+    fn map_is_rendered() {
+        let lines = Day05::parse(include_str!("../input/test.input")).unwrap();
+        let straight: Vec<&Line> = lines
+            .iter()
+            .filter(|l| l.is_horizontal() || l.is_vertical())
+            .collect();
+        assert_snapshot!("straight_lines", Map::new(&straight));
+
+        let all: Vec<&Line> = lines.iter().collect();
+        assert_snapshot!("all_lines", Map::new(&all));
+    }
+}
+
+aoc_tests!(
+    Day05,
+    test => (5, 12),
+    puzzle => (5442, 19571),
+);
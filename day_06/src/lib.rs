@@ -1,27 +1,31 @@
 use im_rc::Vector;
-use itertools::Itertools;
 use tailcall::tailcall;
+use tracing::trace_span;
+use utils::aoc_tests;
 use utils::input::*;
-use utils::output::*;
 use utils::results::*;
+use utils::solution::*;
 
-#[macro_use]
-extern crate log;
+pub struct Day06;
 
-fn main() {
-    env_logger::builder().format_timestamp(None).init();
+impl Solution for Day06 {
+    const DAY: u8 = 6;
 
-    let day = parse_day(file!());
-    let file = file_name_from_args();
-    let contents: String = read_string_from_file(&path_for_day(day, &file).unwrap()).unwrap();
-    let fish: Vec<u8> = contents
-        .split(',')
-        .map(|p| p.parse::<u8>())
-        .try_collect()
-        .unwrap();
+    type Input = Vec<u8>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    info!("Solution to part one: {}", display_result(part_one(&fish)));
-    info!("Solution to part two: {}", display_result(part_two(&fish)));
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_delimited(input, ',')
+    }
+
+    fn part_one(fish: &Self::Input) -> Result<Self::PartOne> {
+        part_one(fish)
+    }
+
+    fn part_two(fish: &Self::Input) -> Result<Self::PartTwo> {
+        part_two(fish)
+    }
 }
 
 fn part_one(fish: &[u8]) -> Result<usize> {
@@ -29,11 +33,12 @@ fn part_one(fish: &[u8]) -> Result<usize> {
 }
 
 fn part_two(fish: &[u8]) -> Result<usize> {
-    let grouped = fish.iter().counts();
-    let fish_counts: Vector<usize> = (0..9)
-        .map(|i| grouped.get(&i).unwrap_or(&0))
-        .copied()
-        .collect();
+    let mut fish_counts = [0; 9];
+    for &timer in fish {
+        *fish_counts.get_mut(timer as usize).ok_or_else(|| {
+            AOCError::InvalidInput(format!("Timer {} is larger than 8", timer))
+        })? += 1;
+    }
     Ok(simulate_group(fish_counts, 256))
 }
 
@@ -52,7 +57,7 @@ fn simulate(fish: Vector<u8>, iterations: usize) -> usize {
             }
             None => {
                 let mut result = fish.clone();
-                result.append(std::iter::repeat(8).take(extra_fishes).collect());
+                result.append(std::iter::repeat_n(8, extra_fishes).collect());
                 result
             }
         }
@@ -61,19 +66,26 @@ fn simulate(fish: Vector<u8>, iterations: usize) -> usize {
     if iterations == 0 {
         fish.len()
     } else {
+        let _span = trace_span!("generation", remaining = iterations, fish = fish.len()).entered();
         let next_iter = traverse(0, fish.clone(), 0);
         simulate(next_iter, iterations - 1)
     }
 }
 
 #[tailcall]
-fn simulate_group(mut fish_count: Vector<usize>, iterations: usize) -> usize {
+fn simulate_group(mut fish_count: [usize; 9], iterations: usize) -> usize {
     if iterations == 0 {
         fish_count.iter().sum()
     } else {
-        let reproduce_count = fish_count.pop_front().unwrap();
-        fish_count.push_back(reproduce_count);
-        *fish_count.get_mut(6).unwrap() += reproduce_count;
+        fish_count.rotate_left(1);
+        fish_count[6] += fish_count[8];
         simulate_group(fish_count, iterations - 1)
     }
 }
+
+aoc_tests!(
+    Day06,
+    test => (5934, 26984457539),
+    #[ignore = "slow in debug builds"]
+    puzzle => (386536, 1732821262171),
+);
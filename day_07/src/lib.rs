@@ -0,0 +1,112 @@
+use im_rc::Vector;
+use itertools::Itertools;
+use utils::aoc_tests;
+use utils::input::*;
+use utils::results::*;
+use utils::solution::*;
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<u32>;
+    type PartOne = u64;
+    type PartTwo = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_delimited(input, ',')
+    }
+
+    fn part_one(positions: &Self::Input) -> Result<Self::PartOne> {
+        part_one(positions)
+    }
+
+    fn part_two(positions: &Self::Input) -> Result<Self::PartTwo> {
+        part_two(positions)
+    }
+}
+
+fn part_one(positions: &[u32]) -> Result<u64> {
+    let (min, max) = positions
+        .iter()
+        .minmax()
+        .into_option()
+        .ok_or_else(|| AOCError::invalid_input("Empty list of positions"))?;
+    let minmax = (*min..max + 1).map(|pos| fuel_for(positions, pos)).minmax();
+
+    minmax
+        .into_option()
+        .map(|(min, _)| min)
+        .ok_or_else(|| AOCError::invalid_input("Empty list of positions"))
+}
+
+fn fuel_for(positions: &[u32], position: u32) -> u64 {
+    let sum: i64 = positions
+        .iter()
+        .map(|&pos| (pos as i64 - position as i64).abs())
+        .sum();
+    sum as u64
+}
+
+fn incr_fuel_for(positions: &[u32], position: u32, current_min: u64) -> Option<u64> {
+    let result: itertools::FoldWhile<u64> = positions.iter().fold_while(0, |sum, &pos| {
+        let new_sum =
+            (0..(position as i64 - pos as i64).unsigned_abs()).fold(sum, |sum, i| sum + i + 1);
+        if new_sum >= current_min {
+            itertools::FoldWhile::Done(new_sum)
+        } else {
+            itertools::FoldWhile::Continue(new_sum)
+        }
+    });
+
+    Some(result.into_inner()).filter(|&new| new <= current_min)
+}
+
+fn part_two(positions: &[u32]) -> Result<u64> {
+    let sorted: Vector<&u32> = positions.iter().sorted().collect();
+    let (first, last) = sorted
+        .front()
+        .zip(sorted.back())
+        .ok_or_else(|| AOCError::invalid_input("Empty list of positions"))?;
+    let start_position = *sorted[sorted.len() / 2];
+    let candidates =
+        (**first..=**last).sorted_by_key(|&pos| (start_position as i64 - pos as i64).abs());
+
+    let min = candidates.fold(u64::MAX, |min, pos| {
+        incr_fuel_for(positions, pos, min).unwrap_or(min)
+    });
+
+    Ok(min)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn empty_positions_are_rejected() {
+        assert_eq!(
+            part_one(&[]),
+            Err(AOCError::invalid_input("Empty list of positions"))
+        );
+        assert_eq!(
+            part_two(&[]),
+            Err(AOCError::invalid_input("Empty list of positions"))
+        );
+    }
+
+    #[test]
+    fn single_position_needs_no_fuel() {
+        assert_eq!(part_one(&[5]), Ok(0));
+        assert_eq!(part_two(&[5]), Ok(0));
+    }
+}
+
+aoc_tests!(
+    Day07,
+    test => (37, 168),
+    #[ignore = "slow in debug builds"]
+    puzzle => (349769, 99540554),
+);
@@ -1,23 +1,34 @@
+use aoc_derive::AocParse;
 use im_rc::HashSet;
 use itertools::Itertools;
 use std::fmt::Display;
 use std::str::FromStr;
+use utils::aoc_tests;
 use utils::input::*;
-use utils::output::*;
+use utils::parser::*;
 use utils::results::*;
+use utils::solution::*;
 
-#[macro_use]
-extern crate log;
+pub struct Day08;
 
-fn main() {
-    env_logger::builder().format_timestamp(None).init();
+impl Solution for Day08 {
+    const DAY: u8 = 8;
 
-    let day = parse_day(file!());
-    let file = file_name_from_args();
-    let notes: Vec<Note> = parse_lines_from_file(&path_for_day(day, &file).unwrap()).unwrap();
+    type Input = Vec<Note>;
+    type PartOne = usize;
+    type PartTwo = usize;
 
-    info!("Solution to part one: {}", display_result(part_one(&notes)));
-    info!("Solution to part two: {}", display_result(part_two(&notes)));
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lines(input)
+    }
+
+    fn part_one(notes: &Self::Input) -> Result<Self::PartOne> {
+        part_one(notes)
+    }
+
+    fn part_two(notes: &Self::Input) -> Result<Self::PartTwo> {
+        part_two(notes)
+    }
 }
 
 fn part_one(notes: &[Note]) -> Result<usize> {
@@ -49,14 +60,10 @@ enum Signal {
     G,
 }
 
-impl FromStr for Signal {
-    type Err = AOCError;
+impl TryFrom<char> for Signal {
+    type Error = AOCError;
 
-    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
-        let ch = input
-            .chars()
-            .exactly_one()
-            .map_err(|_| AOCError::new_from_ref("Input to Signal wasn't exactly one char"))?;
+    fn try_from(ch: char) -> std::result::Result<Self, Self::Error> {
         match ch {
             'a' => Ok(Signal::A),
             'b' => Ok(Signal::B),
@@ -65,7 +72,10 @@ impl FromStr for Signal {
             'e' => Ok(Signal::E),
             'f' => Ok(Signal::F),
             'g' => Ok(Signal::G),
-            _ => Err(AOCError::new_from_ref("Unsupported signal char")),
+            _ => Err(AOCError::parse(
+                &ch.to_string(),
+                "expected a signal from a to g",
+            )),
         }
     }
 }
@@ -124,24 +134,36 @@ impl Display for SignalPattern {
     }
 }
 
+#[derive(Debug, AocParse)]
+#[aoc(pattern = "{digit_patterns} | {output_patterns}")]
+pub struct Note {
+    #[aoc(separator = " ")]
+    digit_patterns: Vec<SignalPattern>,
+    #[aoc(separator = " ")]
+    output_patterns: Vec<SignalPattern>,
+}
+
+fn signal<'a>() -> impl Parser<'a, Signal> {
+    map_res(
+        char_where(|c| !c.is_whitespace(), "a signal from a to g"),
+        Signal::try_from,
+    )
+}
+
+fn signal_pattern<'a>() -> impl Parser<'a, SignalPattern> {
+    map(many1(signal()), |signals| {
+        SignalPattern::new(HashSet::from(signals))
+    })
+}
+
 impl FromStr for SignalPattern {
     type Err = AOCError;
 
     fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
-        let signals: Vec<Signal> = input
-            .chars()
-            .map(|c| c.to_string().parse::<Signal>())
-            .try_collect()?;
-        Ok(SignalPattern::new(HashSet::from(signals)))
+        parse_all(signal_pattern(), input)
     }
 }
 
-#[derive(Debug)]
-struct Note {
-    digit_patterns: Vec<SignalPattern>,
-    output_patterns: Vec<SignalPattern>,
-}
-
 impl Note {
     fn find_pattern_with_len(&self, length: usize) -> Result<&SignalPattern> {
         self.digit_patterns
@@ -149,7 +171,7 @@ impl Note {
             .filter(|d| d.signals.len() == length)
             .exactly_one()
             .map_err(|err| {
-                AOCError::new(format!(
+                AOCError::NoSolution(format!(
                     "Could not find unique pattern with length {}: {}",
                     length, err
                 ))
@@ -183,7 +205,7 @@ impl Note {
                 .copied()
                 .exactly_one()
                 .map_err(|e| {
-                    AOCError::new(format!(
+                    AOCError::NoSolution(format!(
                         "Couldn't find pattern overlapping with {} {}: {}",
                         overlap, reference, e
                     ))
@@ -197,7 +219,7 @@ impl Note {
                 .iter()
                 .copied()
                 .exactly_one()
-                .map_err(|e| AOCError::new(e.to_string()))
+                .map_err(|e| AOCError::NoSolution(e.to_string()))
         }
 
         let mut five_signals: HashSet<&SignalPattern> = self.find_patterns_with_len(5);
@@ -228,7 +250,7 @@ impl Note {
                 .find_map(|(value, pattern)| {
                     Some(value).filter(|_| pattern.is_equivalent_to(input))
                 })
-                .ok_or_else(|| AOCError::new(format!("Output pattern {} not found", input)))
+                .ok_or_else(|| AOCError::NoSolution(format!("Output pattern {} not found", input)))
         };
 
         let digits: Vec<usize> = self.output_patterns.iter().map(decode).try_collect()?;
@@ -254,27 +276,31 @@ impl Display for Note {
     }
 }
 
-impl FromStr for Note {
-    type Err = AOCError;
-
-    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
-        let (signal_string, output_string) = input
-            .split(" | ")
-            .collect_tuple()
-            .ok_or_else(|| AOCError::new_from_ref("Malformed input"))?;
-
-        let digits = signal_string
-            .split_whitespace()
-            .map(|string| string.parse::<SignalPattern>())
-            .try_collect()?;
-        let outputs = output_string
-            .split_whitespace()
-            .map(|string| string.parse::<SignalPattern>())
-            .try_collect()?;
-
-        Ok(Note {
-            digit_patterns: digits,
-            output_patterns: outputs,
-        })
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn note_is_parsed() {
+        let note: Note = "ab dab | ba bad".parse().unwrap();
+        assert_eq!(note.digit_patterns.len(), 2);
+        assert!(note.output_patterns[0].is_equivalent_to(&note.digit_patterns[0]));
+        assert!(note.output_patterns[1].is_equivalent_to(&note.digit_patterns[1]));
+
+        assert_eq!(
+            "ab dab | bx".parse::<Note>().unwrap_err(),
+            AOCError::parse("x", "expected a signal from a to g or end of input")
+        );
+        assert_eq!(
+            "ab dab ba".parse::<Note>().unwrap_err(),
+            AOCError::parse("ab dab ba", "expected ' | '")
+        );
     }
 }
+
+aoc_tests!(
+    Day08,
+    test => (26, 61229),
+    puzzle => (421, 986163),
+);
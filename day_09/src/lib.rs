@@ -0,0 +1,191 @@
+use ansi_term::Color;
+use im_rc::hashset;
+use im_rc::HashMap;
+use im_rc::HashSet;
+use itertools::Itertools;
+use std::fmt::Display;
+use std::str::FromStr;
+use tailcall::tailcall;
+use tracing::debug;
+use tracing::instrument;
+use tracing::trace_span;
+use utils::aoc_tests;
+use utils::grid::*;
+use utils::output::paint;
+use utils::results::*;
+use utils::solution::*;
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+
+    type Input = HeightMap;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.parse()
+    }
+
+    fn part_one(map: &Self::Input) -> Result<Self::PartOne> {
+        part_one(map)
+    }
+
+    fn part_two(map: &Self::Input) -> Result<Self::PartTwo> {
+        part_two(map)
+    }
+}
+
+fn part_one(map: &HeightMap) -> Result<usize> {
+    Ok(map.minima().map(|(_, h)| h + 1).sum())
+}
+
+fn part_two(map: &HeightMap) -> Result<usize> {
+    let basins = find_basins(map);
+
+    debug!("Basins:\n{}", colorize(map, &basins));
+    let product = basins
+        .iter()
+        .map(|b| b.len())
+        .sorted_by_key(|&s| -(s as i64))
+        .take(3)
+        .product();
+    Ok(product)
+}
+
+#[instrument(level = "debug", skip_all)]
+fn find_basins(map: &HeightMap) -> Vec<HashSet<(usize, usize)>> {
+    #[tailcall]
+    fn calculate_basin(
+        map: &HeightMap,
+        seeds: HashSet<(usize, usize)>,
+        basin_positions: HashSet<(usize, usize)>,
+    ) -> HashSet<(usize, usize)> {
+        match seeds.iter().next() {
+            None => basin_positions,
+            Some(seed) => {
+                let next_points: HashSet<(usize, usize)> = map
+                    .neighbours_of(&seed.0, &seed.1)
+                    .filter(|&(_, h)| *h < 9)
+                    .map(|(p, _)| p)
+                    .filter(|pos| !basin_positions.contains(pos))
+                    .collect();
+
+                calculate_basin(
+                    map,
+                    seeds.without(seed).union(next_points),
+                    basin_positions.update(*seed),
+                )
+            }
+        }
+    }
+
+    map.minima()
+        .map(|(pos, _)| {
+            let _span = trace_span!("basin", low_point = ?pos).entered();
+            calculate_basin(map, hashset![pos], HashSet::new())
+        })
+        .collect()
+}
+
+/// Renders the height map with every basin in its own colour and its lowest point in bold.
+fn colorize(map: &HeightMap, basins: &[HashSet<(usize, usize)>]) -> String {
+    let colours = vec![
+        Color::Blue,
+        Color::Cyan,
+        Color::Green,
+        Color::Purple,
+        Color::Red,
+        Color::White,
+        Color::Yellow,
+    ];
+
+    let color_map: HashMap<(usize, usize), Color> = basins
+        .iter()
+        .zip(colours.into_iter().cycle())
+        .flat_map(|(basin, color)| basin.into_iter().map(move |&pos| (pos, color)))
+        .collect();
+    let minima: HashSet<(usize, usize)> = map.minima().map(|(pos, _)| pos).collect();
+
+    let border = ansi_term::Style::new().bold().on(Color::Black);
+
+    map.heights
+        .rows()
+        .enumerate()
+        .map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(x, v)| {
+                    let style = color_map
+                        .get(&(x, y))
+                        .map(|&color| {
+                            if minima.contains(&(x, y)) {
+                                ansi_term::Style::new().fg(color).bold()
+                            } else {
+                                ansi_term::Style::new().fg(color).dimmed()
+                            }
+                        })
+                        .unwrap_or(border);
+                    paint(style, v)
+                })
+                .join("")
+        })
+        .join("\n")
+}
+
+#[derive(Debug)]
+pub struct HeightMap {
+    heights: Grid<usize>,
+}
+
+impl HeightMap {
+    fn neighbours_of(&self, x: &usize, y: &usize) -> impl Iterator<Item = (Position, &usize)> {
+        self.heights.neighbours(*x, *y)
+    }
+
+    fn minima(&self) -> impl Iterator<Item = (Position, usize)> + '_ {
+        self.heights.iter().filter_map(|((x, y), height)| {
+            self.neighbours_of(&x, &y)
+                .all(|(_, n_height)| n_height > height)
+                .then_some(((x, y), *height))
+        })
+    }
+}
+
+impl Display for HeightMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{}", self.heights)
+    }
+}
+
+impl FromStr for HeightMap {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        Grid::parse_digits(input).map(|heights| HeightMap { heights })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use utils::assert_snapshot;
+    use utils::output::set_color;
+
+    #[test]
+    fn map_and_basins_are_rendered() {
+        let map: HeightMap = include_str!("../input/test.input").parse().unwrap();
+        assert_snapshot!("height_map", map);
+
+        set_color(true);
+        assert_snapshot!("basins", colorize(&map, &find_basins(&map)));
+    }
+}
+
+aoc_tests!(
+    Day09,
+    test => (15, 1134),
+    puzzle => (539, 736920),
+);
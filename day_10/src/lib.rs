@@ -0,0 +1,272 @@
+use im_rc::Vector;
+use itertools::process_results;
+use itertools::Itertools;
+use std::io::BufRead;
+use tailcall::tailcall;
+use tracing::debug;
+use tracing::instrument;
+use tracing::trace;
+use utils::aoc_tests;
+use utils::input::*;
+use utils::results::*;
+use utils::solution::*;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = Vec<String>;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(read_lines(input))
+    }
+
+    fn part_one(lines: &Self::Input) -> Result<Self::PartOne> {
+        part_one(lines)
+    }
+
+    fn part_two(lines: &Self::Input) -> Result<Self::PartTwo> {
+        part_two(lines)
+    }
+
+    const STREAMING: bool = true;
+
+    fn part_one_from_reader(reader: &mut dyn BufRead) -> Result<Self::PartOne> {
+        part_one_from_reader(reader)
+    }
+
+    fn part_two_from_reader(reader: &mut dyn BufRead) -> Result<Self::PartTwo> {
+        part_two_from_reader(reader)
+    }
+}
+
+fn part_one(lines: &[String]) -> Result<usize> {
+    syntax_error_score(lines.iter())
+}
+
+fn part_two(lines: &[String]) -> Result<usize> {
+    middle_completion_score(lines.iter())
+}
+
+fn part_one_from_reader<R: BufRead>(reader: R) -> Result<usize> {
+    process_results(parse_lines_iter::<String, _>(reader), |lines| {
+        syntax_error_score(lines)
+    })?
+}
+
+fn part_two_from_reader<R: BufRead>(reader: R) -> Result<usize> {
+    process_results(parse_lines_iter::<String, _>(reader), |lines| {
+        middle_completion_score(lines)
+    })?
+}
+
+fn syntax_error_score<S: AsRef<str>, I: Iterator<Item = S>>(lines: I) -> Result<usize> {
+    lines.map(|line| corruption_score(line.as_ref())).sum()
+}
+
+fn middle_completion_score<S: AsRef<str>, I: Iterator<Item = S>>(lines: I) -> Result<usize> {
+    let scores: Vec<Option<usize>> = lines
+        .map(|line| completion_score(line.as_ref()))
+        .try_collect()?;
+    let scores: Vec<usize> = scores.into_iter().flatten().sorted().collect();
+
+    debug!("Scores: {:?}", scores);
+    scores
+        .get(scores.len() / 2)
+        .copied()
+        .ok_or_else(|| AOCError::no_solution("Error fetching middle result"))
+}
+
+#[instrument(level = "debug", ret)]
+fn corruption_score(line: &str) -> Result<usize> {
+    match validate(line)? {
+        LineResult::Corrupt(chars) => chars.iter().map(illegal_char_points).sum(),
+        _ => Ok(0),
+    }
+}
+
+#[instrument(level = "debug", ret)]
+fn completion_score(line: &str) -> Result<Option<usize>> {
+    match validate(line)? {
+        LineResult::Incomplete(chars) => {
+            trace!(required = ?chars, "incomplete line");
+            let line_score = chars.iter().try_fold(0, |score, ch| {
+                required_char_points(ch).map(|points| score * 5 + points)
+            })?;
+            Ok(Some(line_score))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn validate(string: &str) -> Result<LineResult> {
+    #[tailcall]
+    fn validate(
+        line: &str,
+        mut input: Vector<char>,
+        mut stack: Vector<char>,
+        mut illegal_chars: Vector<char>,
+    ) -> Result<LineResult> {
+        match input.pop_front() {
+            Some(found_open) if is_open_char(found_open) => {
+                stack.push_front(found_open);
+                trace!(
+                    "New opening char {}, adding to stack {:?}",
+                    found_open,
+                    stack
+                );
+                validate(line, input, stack, illegal_chars)
+            }
+            Some(found) if !is_close_char(found) => Err(AOCError::parse(
+                line,
+                &format!("unsupported character '{}'", found),
+            )),
+            Some(found_closed) => match stack.pop_front() {
+                None => Err(AOCError::parse(
+                    line,
+                    &format!("'{}' closes a chunk that was never opened", found_closed),
+                )),
+                Some(last_open) if close_char_for(&last_open)? == found_closed => {
+                    trace!(
+                        "Found {} and that corresponds to close for {}",
+                        found_closed,
+                        last_open
+                    );
+                    validate(line, input, stack, illegal_chars)
+                }
+                Some(last_open) => {
+                    trace!(
+                        "Found '{}' and expected '{:?}' (based on {})",
+                        found_closed,
+                        close_char_for(&last_open),
+                        last_open
+                    );
+                    illegal_chars.push_back(found_closed);
+                    validate(line, input, stack, illegal_chars)
+                }
+            },
+            None => {
+                if stack.is_empty() && illegal_chars.is_empty() {
+                    Ok(LineResult::Valid)
+                } else if illegal_chars.is_empty() {
+                    let required_chars = stack.iter().map(close_char_for).try_collect()?;
+                    Ok(LineResult::Incomplete(required_chars))
+                } else {
+                    Ok(LineResult::Corrupt(illegal_chars))
+                }
+            }
+        }
+    }
+
+    validate(
+        string,
+        string.chars().collect(),
+        Vector::new(),
+        Vector::new(),
+    )
+}
+
+fn close_char_for(open_char: &char) -> Result<char> {
+    match open_char {
+        '{' => Ok('}'),
+        '(' => Ok(')'),
+        '[' => Ok(']'),
+        '<' => Ok('>'),
+        c => Err(AOCError::parse(&c.to_string(), "unsupported open char")),
+    }
+}
+
+fn illegal_char_points(c: &char) -> Result<usize> {
+    match c {
+        ')' => Ok(3),
+        ']' => Ok(57),
+        '}' => Ok(1197),
+        '>' => Ok(25137),
+        c => Err(AOCError::parse(&c.to_string(), "unsupported illegal char")),
+    }
+}
+
+fn required_char_points(c: &char) -> Result<usize> {
+    match c {
+        ')' => Ok(1),
+        ']' => Ok(2),
+        '}' => Ok(3),
+        '>' => Ok(4),
+        c => Err(AOCError::parse(&c.to_string(), "unsupported required char")),
+    }
+}
+
+fn is_open_char(open_char: char) -> bool {
+    matches![open_char, '{' | '<' | '(' | '[']
+}
+
+fn is_close_char(close_char: char) -> bool {
+    matches![close_char, '}' | '>' | ')' | ']']
+}
+
+#[derive(Debug, PartialEq)]
+enum LineResult {
+    Valid,
+    Incomplete(Vector<char>),
+    Corrupt(Vector<char>),
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    const EXAMPLE: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]
+";
+
+    #[test]
+    fn lines_are_scored() {
+        assert_eq!(corruption_score("{([(<{}[<>[]}>{[]{[(<()>"), Ok(1197));
+        assert_eq!(corruption_score("[({(<(())[]>[[{[]{<()<>>"), Ok(0));
+        assert_eq!(
+            completion_score("[({(<(())[]>[[{[]{<()<>>"),
+            Ok(Some(288957))
+        );
+        assert_eq!(completion_score("{([(<{}[<>[]}>{[]{[(<()>"), Ok(None));
+    }
+
+    #[test]
+    fn invalid_lines_are_rejected() {
+        assert_eq!(
+            corruption_score("(<x>)"),
+            Err(AOCError::parse("(<x>)", "unsupported character 'x'"))
+        );
+        assert_eq!(
+            completion_score("()]"),
+            Err(AOCError::parse(
+                "()]",
+                "']' closes a chunk that was never opened"
+            ))
+        );
+        assert!(part_one_from_reader("()\n(]x\n".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn stream_is_solved() {
+        assert_eq!(part_one_from_reader(EXAMPLE.as_bytes()), Ok(26397));
+        assert_eq!(part_two_from_reader(EXAMPLE.as_bytes()), Ok(288957));
+    }
+}
+
+aoc_tests!(
+    Day10,
+    test => (26397, 288957),
+    puzzle => (390993, 2391385187),
+);
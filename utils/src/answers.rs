@@ -0,0 +1,180 @@
+use crate::results::AOCError;
+use crate::results::Result;
+use crate::solution::Outcome;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Answer::Number(number) => write!(f, "{}", number),
+            Answer::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(answer: &str) -> Answer {
+        answer
+            .parse::<i64>()
+            .map(Answer::Number)
+            .unwrap_or_else(|_| Answer::Text(answer.to_owned()))
+    }
+}
+
+#[derive(Debug, PartialEq, Default, Clone, Serialize, Deserialize)]
+pub struct Expected {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_one: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part_two: Option<Answer>,
+}
+
+/// The known correct answers, keyed by day directory and input name.
+#[derive(Debug)]
+pub struct ExpectedAnswers {
+    path: PathBuf,
+    days: BTreeMap<String, BTreeMap<String, Expected>>,
+}
+
+impl ExpectedAnswers {
+    pub fn load(path: &Path) -> Result<ExpectedAnswers> {
+        let days = if path.exists() {
+            toml::from_str(&fs::read_to_string(path)?)
+                .map_err(|err| AOCError::parse_with_source(&path.display().to_string(), err))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(ExpectedAnswers {
+            path: path.to_path_buf(),
+            days,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let contents = toml::to_string(&self.days)
+            .map_err(|err| AOCError::new(format!("Failed to serialize answers: {}", err)))?;
+        fs::write(&self.path, contents)?;
+        Ok(())
+    }
+
+    pub fn inputs_for(&self, day: &str) -> impl Iterator<Item = (&String, &Expected)> {
+        self.days
+            .get(day)
+            .into_iter()
+            .flat_map(|inputs| inputs.iter())
+    }
+
+    pub fn days(&self) -> impl Iterator<Item = &String> {
+        self.days.keys()
+    }
+
+    pub fn set(&mut self, day: &str, input: &str, expected: Expected) {
+        self.days
+            .entry(day.to_owned())
+            .or_default()
+            .insert(input.to_owned(), expected);
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Pass,
+    Fail {
+        expected: Answer,
+        actual: Result<String>,
+    },
+    Unknown,
+}
+
+impl Check {
+    pub fn new(expected: &Option<Answer>, actual: Option<Result<String>>) -> Check {
+        match (expected, actual) {
+            (None, _) => Check::Unknown,
+            (Some(expected), Some(Ok(actual))) if Answer::from(actual.as_str()) == *expected => {
+                Check::Pass
+            }
+            (Some(expected), actual) => Check::Fail {
+                expected: expected.clone(),
+                actual: actual.unwrap_or_else(|| Err(AOCError::no_solution("Not computed"))),
+            },
+        }
+    }
+
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Check::Fail { .. })
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Check::Pass => write!(f, "pass"),
+            Check::Unknown => write!(f, "-"),
+            Check::Fail {
+                expected,
+                actual: Ok(actual),
+            } => write!(f, "FAIL (expected {}, got {})", expected, actual),
+            Check::Fail {
+                expected,
+                actual: Err(error),
+            } => write!(f, "FAIL (expected {}, {})", expected, error),
+        }
+    }
+}
+
+pub fn verify(expected: &Expected, outcome: Outcome) -> (Check, Check) {
+    (
+        Check::new(&expected.part_one, outcome.part_one),
+        Check::new(&expected.part_two, outcome.part_two),
+    )
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::solution::Elapsed;
+
+    #[test]
+    fn answers_are_parsed_from_toml() {
+        let days: BTreeMap<String, BTreeMap<String, Expected>> = toml::from_str(
+            "[day_01.test]\npart_one = 7\npart_two = \"5\"\n\n[day_01.puzzle]\npart_one = 1521\n",
+        )
+        .unwrap();
+
+        let test = &days["day_01"]["test"];
+        assert_eq!(test.part_one, Some(Answer::Number(7)));
+        assert_eq!(test.part_two, Some(Answer::Text("5".to_owned())));
+        assert_eq!(days["day_01"]["puzzle"].part_two, None);
+    }
+
+    #[test]
+    fn outcome_is_checked_against_expected_answers() {
+        let expected = Expected {
+            part_one: Some(Answer::Number(7)),
+            part_two: Some(Answer::Number(5)),
+        };
+        let outcome = Outcome {
+            part_one: Some(Ok("7".to_owned())),
+            part_two: Some(Ok("6".to_owned())),
+            elapsed: Elapsed::default(),
+        };
+
+        let (one, two) = verify(&expected, outcome);
+        assert_eq!(one, Check::Pass);
+        assert!(two.is_failure());
+        assert_eq!(Check::new(&None, Some(Ok("1".to_owned()))), Check::Unknown);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::output::display_duration;
+use crate::results::AOCError;
+use crate::results::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct BenchSettings {
+    pub warmup: usize,
+    pub iterations: usize,
+}
+
+/// The measured durations of every iteration, per phase.
+#[derive(Debug, Default)]
+pub struct Samples {
+    parse: Vec<Duration>,
+    part_one: Vec<Duration>,
+    part_two: Vec<Duration>,
+}
+
+impl Samples {
+    pub fn push(&mut self, parse: Duration, part_one: Duration, part_two: Duration) {
+        self.parse.push(parse);
+        self.part_one.push(part_one);
+        self.part_two.push(part_two);
+    }
+
+    pub fn timings(&self) -> Result<Timings> {
+        Ok(Timings {
+            parse: Stats::from_samples(&self.parse)?,
+            part_one: Stats::from_samples(&self.part_one)?,
+            part_two: Stats::from_samples(&self.part_two)?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+pub struct Stats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+    pub min: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Result<Stats> {
+        if samples.is_empty() {
+            return Err(AOCError::invalid_input(
+                "At least one iteration is required",
+            ));
+        }
+        let count = samples.len() as u32;
+        let mean = samples.iter().sum::<Duration>() / count;
+        let nanos = samples
+            .iter()
+            .map(|sample| sample.as_nanos() as f64)
+            .collect();
+        let median = Duration::from_nanos(median(nanos).unwrap_or_default() as u64);
+        let variance = samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        Ok(Stats {
+            mean,
+            median,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            min: samples.iter().copied().min().unwrap_or_default(),
+        })
+    }
+}
+
+/// The middle value, or the mean of the two middle values when there is an even number of them.
+pub fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let middle = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        Some((values[middle - 1] + values[middle]) / 2.0)
+    } else {
+        Some(values[middle])
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "mean {}, median {}, stddev {}, min {}",
+            display_duration(self.mean),
+            display_duration(self.median),
+            display_duration(self.stddev),
+            display_duration(self.min)
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Serialize, Deserialize)]
+pub struct Timings {
+    pub parse: Stats,
+    pub part_one: Stats,
+    pub part_two: Stats,
+}
+
+impl Timings {
+    pub fn phases(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("parse", &self.parse),
+            ("part one", &self.part_one),
+            ("part two", &self.part_two),
+        ]
+    }
+
+    /// The phases whose median is more than `threshold` slower than in `baseline`.
+    pub fn regressions(&self, baseline: &Timings, threshold: f64) -> Vec<Regression> {
+        self.phases()
+            .iter()
+            .zip(baseline.phases().iter())
+            .filter_map(|((phase, current), (_, previous))| {
+                let change = relative_change(previous.median, current.median);
+                (change > threshold).then_some(Regression {
+                    phase,
+                    baseline: previous.median,
+                    current: current.median,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The relative change from `previous` to `current`, e.g. `0.1` for 10% slower.
+pub fn relative_change(previous: Duration, current: Duration) -> f64 {
+    if previous.is_zero() {
+        0.0
+    } else {
+        current.as_secs_f64() / previous.as_secs_f64() - 1.0
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Regression {
+    pub phase: &'static str,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Display for Regression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{} regressed from {} to {} ({:+.1}%)",
+            self.phase,
+            display_duration(self.baseline),
+            display_duration(self.current),
+            relative_change(self.baseline, self.current) * 100.0
+        )
+    }
+}
+
+/// Benchmark results of earlier runs, keyed by day directory and input name.
+#[derive(Debug)]
+pub struct Baseline {
+    path: PathBuf,
+    days: BTreeMap<String, BTreeMap<String, Timings>>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline> {
+        let days = if path.exists() {
+            serde_json::from_str(&fs::read_to_string(path)?)
+                .map_err(|err| AOCError::parse_with_source(&path.display().to_string(), err))?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Baseline {
+            path: path.to_path_buf(),
+            days,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let contents = serde_json::to_string_pretty(&self.days)
+            .map_err(|err| AOCError::new(format!("Failed to serialize baseline: {}", err)))?;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, contents)?;
+        Ok(())
+    }
+
+    pub fn get(&self, day: &str, input: &str) -> Option<&Timings> {
+        self.days.get(day).and_then(|inputs| inputs.get(input))
+    }
+
+    pub fn insert(&mut self, day: &str, input: &str, timings: Timings) {
+        self.days
+            .entry(day.to_owned())
+            .or_default()
+            .insert(input.to_owned(), timings);
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&ms| Duration::from_millis(ms)).collect()
+    }
+
+    #[test]
+    fn stats_are_computed() {
+        let stats = Stats::from_samples(&millis(&[4, 2, 6, 4])).unwrap();
+
+        assert_eq!(stats.mean, Duration::from_millis(4));
+        assert_eq!(stats.median, Duration::from_millis(4));
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.stddev.as_micros(), 1414);
+
+        let stats = Stats::from_samples(&millis(&[9, 1, 2])).unwrap();
+        assert_eq!(stats.median, Duration::from_millis(2));
+        assert!(Stats::from_samples(&[]).is_err());
+
+        assert_eq!(median(vec![3.0, 1.0, 2.0]), Some(2.0));
+        assert_eq!(median(vec![]), None);
+    }
+
+    #[test]
+    fn regressions_are_flagged() {
+        let timings = |parse, one, two| Timings {
+            parse: Stats::from_samples(&millis(&[parse])).unwrap(),
+            part_one: Stats::from_samples(&millis(&[one])).unwrap(),
+            part_two: Stats::from_samples(&millis(&[two])).unwrap(),
+        };
+        let baseline = timings(10, 10, 10);
+
+        assert_eq!(timings(10, 11, 9).regressions(&baseline, 0.2), vec![]);
+        assert_eq!(
+            timings(10, 13, 9).regressions(&baseline, 0.2),
+            vec![Regression {
+                phase: "part one",
+                baseline: Duration::from_millis(10),
+                current: Duration::from_millis(13),
+            }]
+        );
+
+        let mut saved = Baseline {
+            path: PathBuf::new(),
+            days: BTreeMap::new(),
+        };
+        saved.insert("day_01", "puzzle", baseline);
+        let json = serde_json::to_string(&saved.days).unwrap();
+        let days: BTreeMap<String, BTreeMap<String, Timings>> =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(days["day_01"]["puzzle"], baseline);
+    }
+}
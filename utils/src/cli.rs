@@ -0,0 +1,177 @@
+use crate::input::InputSource;
+use crate::output::init_color;
+use crate::output::ColorChoice;
+use crate::output::Format;
+use crate::results::Result;
+use crate::solution::Part;
+use crate::trace::init_tracing;
+use crate::trace::LogStyle;
+use crate::trace::TraceGuard;
+use crate::trace::TraceSettings;
+use clap::Args;
+use std::path::PathBuf;
+use tracing::level_filters::LevelFilter;
+
+pub const PUZZLE_INPUT: &str = "puzzle";
+
+/// Flags controlling logging and how results are reported, shared by every command.
+#[derive(Args, Debug)]
+pub struct OutputArgs {
+    /// Log level, overrides `RUST_LOG`: off, error, warn, info, debug or trace
+    #[clap(long, global = true)]
+    pub log_level: Option<LevelFilter>,
+
+    /// Output format for answers: text, json or jsonl (one record per line)
+    #[clap(long, global = true, default_value = "text")]
+    pub format: Format,
+
+    /// When to colour output: auto, always or never; auto honours `NO_COLOR` and needs a terminal
+    #[clap(long, global = true, default_value = "auto", value_name = "WHEN")]
+    pub color: ColorChoice,
+
+    /// Disable coloured output, same as `--color never`
+    #[clap(long, global = true)]
+    pub no_color: bool,
+
+    /// How to write logs: flat lines, or a tree of the spans they happened in
+    #[clap(long, global = true, default_value = "flat", value_name = "STYLE")]
+    pub log_style: LogStyle,
+
+    /// Write every span to this file as a Chrome trace, viewable in `chrome://tracing` or Perfetto
+    #[clap(long, global = true, value_name = "FILE")]
+    pub chrome_trace: Option<PathBuf>,
+}
+
+impl OutputArgs {
+    pub fn color(&self) -> ColorChoice {
+        if self.no_color {
+            ColorChoice::Never
+        } else {
+            self.color
+        }
+    }
+
+    /// Sets up colour and tracing; keep the guard until the command is done.
+    pub fn init(&self) -> Result<TraceGuard> {
+        init_color(self.color());
+        init_tracing(&TraceSettings {
+            level: self.log_level,
+            style: self.log_style,
+            chrome: self.chrome_trace.clone(),
+        })
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct InputArgs {
+    /// Name of the input file in `day_XX/input/`, a path to any file or `-` for stdin
+    #[clap(short, long, default_value = PUZZLE_INPUT)]
+    pub input: InputSource,
+
+    /// Path to an input file, also when it has no separator or extension
+    #[clap(long, conflicts_with_all = &["input", "input-text"])]
+    pub input_file: Option<PathBuf>,
+
+    /// Literal puzzle input, used instead of a file
+    #[clap(long, conflicts_with = "input")]
+    pub input_text: Option<String>,
+}
+
+impl InputArgs {
+    pub fn source(&self) -> InputSource {
+        match (&self.input_text, &self.input_file) {
+            (Some(text), _) => InputSource::Literal(text.clone()),
+            (None, Some(path)) => InputSource::Path(path.clone()),
+            (None, None) => self.input.clone(),
+        }
+    }
+}
+
+/// Flags selecting what to solve for a day.
+#[derive(Args, Debug)]
+pub struct SolveArgs {
+    #[clap(flatten)]
+    pub input: InputArgs,
+
+    /// Part to run: 1, 2 or both
+    #[clap(short, long, default_value = "both")]
+    pub part: Part,
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[clap(flatten)]
+        output: OutputArgs,
+
+        #[clap(flatten)]
+        solve: SolveArgs,
+    }
+
+    #[test]
+    fn flags_are_parsed() {
+        let cli = Cli::try_parse_from([
+            "aoc",
+            "--part",
+            "2",
+            "--input",
+            "-",
+            "--log-level",
+            "debug",
+            "--format",
+            "json",
+            "--no-color",
+            "--log-style",
+            "tree",
+            "--chrome-trace",
+            "trace.json",
+        ])
+        .unwrap();
+
+        assert_eq!(cli.solve.part, Part::Two);
+        assert_eq!(cli.solve.input.source(), InputSource::Stdin);
+        assert_eq!(cli.output.log_level, Some(LevelFilter::DEBUG));
+        assert_eq!(cli.output.format, Format::Json);
+        assert!(cli.output.no_color);
+        assert_eq!(cli.output.color(), ColorChoice::Never);
+        assert_eq!(cli.output.log_style, LogStyle::Tree);
+        assert_eq!(cli.output.chrome_trace, Some(PathBuf::from("trace.json")));
+    }
+
+    #[test]
+    fn defaults_are_applied() {
+        let cli = Cli::try_parse_from(["aoc", "--input-text", "1,2,3"]).unwrap();
+
+        assert_eq!(cli.solve.part, Part::Both);
+        assert_eq!(
+            cli.solve.input.source(),
+            InputSource::Literal("1,2,3".to_owned())
+        );
+        assert_eq!(cli.output.log_level, None);
+        assert_eq!(cli.output.format, Format::Text);
+        assert!(!cli.output.no_color);
+        assert_eq!(cli.output.color(), ColorChoice::Auto);
+        assert_eq!(cli.output.log_style, LogStyle::Flat);
+        assert_eq!(cli.output.chrome_trace, None);
+
+        let cli = Cli::try_parse_from(["aoc", "--input-file", "generated"]).unwrap();
+        assert_eq!(
+            cli.solve.input.source(),
+            InputSource::Path(PathBuf::from("generated"))
+        );
+        assert!(
+            Cli::try_parse_from(["aoc", "--input-file", "generated", "--input", "test"]).is_err()
+        );
+
+        let cli = Cli::try_parse_from(["aoc", "--color", "always"]).unwrap();
+        assert_eq!(cli.output.color(), ColorChoice::Always);
+
+        assert!(Cli::try_parse_from(["aoc", "--format", "xml"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "-i", "test", "--input-text", "1"]).is_err());
+    }
+}
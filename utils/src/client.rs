@@ -0,0 +1,107 @@
+use crate::results::AOCError;
+use crate::results::Result;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+pub const YEAR: u16 = 2021;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_ENV: &str = "AOC_SESSION";
+const BASE_URL_ENV: &str = "AOC_BASE_URL";
+const USER_AGENT: &str = "github.com/rutgerclaes/2021-advent-of-code-rs";
+
+/// Looks up the session token in `AOC_SESSION`, falling back to `~/.config/aoc/session`.
+pub fn session_from_env() -> Result<String> {
+    env::var(SESSION_ENV)
+        .ok()
+        .or_else(|| {
+            session_config_path()
+                .and_then(|path| fs::read_to_string(path).ok())
+                .map(|contents| contents.trim().to_owned())
+        })
+        .filter(|session| !session.is_empty())
+        .ok_or_else(|| {
+            AOCError::new(format!(
+                "No session token found, set {} or write it to ~/.config/aoc/session",
+                SESSION_ENV
+            ))
+        })
+}
+
+fn session_config_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| Path::new(&home).join(".config/aoc/session"))
+}
+
+pub fn base_url_from_env() -> String {
+    env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned())
+}
+
+#[derive(Debug, Clone)]
+pub struct Client {
+    base_url: String,
+    session: String,
+    year: u16,
+}
+
+impl Client {
+    pub fn new(session: String) -> Client {
+        Client {
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            session,
+            year: YEAR,
+        }
+    }
+
+    pub fn from_env() -> Result<Client> {
+        Ok(Client::new(session_from_env()?).with_base_url(&base_url_from_env()))
+    }
+
+    pub fn with_base_url(self, base_url: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            ..self
+        }
+    }
+
+    pub fn with_year(self, year: u16) -> Client {
+        Client { year, ..self }
+    }
+
+    pub(crate) fn day_url(&self, day: u8, path: &str) -> String {
+        format!("{}/{}/day/{}/{}", self.base_url, self.year, day, path)
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        ureq::request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+    }
+
+    pub(crate) fn get(&self, url: &str) -> Result<String> {
+        self.request("GET", url)
+            .call()
+            .map_err(|err| request_error(url, err))?
+            .into_string()
+            .map_err(AOCError::from)
+    }
+
+    pub(crate) fn post_form(&self, url: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.request("POST", url)
+            .send_form(form)
+            .map_err(|err| request_error(url, err))?
+            .into_string()
+            .map_err(AOCError::from)
+    }
+}
+
+/// Failing to reach the site is an I/O error, an error status from the site is not.
+fn request_error(url: &str, error: ureq::Error) -> AOCError {
+    let message = format!("Request to {} failed: {}", url, error);
+    match error {
+        ureq::Error::Transport(_) => AOCError::Io(io::Error::other(message)),
+        ureq::Error::Status(..) => AOCError::new(message),
+    }
+}
@@ -0,0 +1,183 @@
+use crate::answers::Answer;
+use crate::answers::Expected;
+use crate::answers::ExpectedAnswers;
+use crate::results::AOCError;
+use crate::results::Result;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub input: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Display for Example {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        writeln!(f, "{}", self.input)?;
+        writeln!(f, "part one: {}", self.part_one.as_deref().unwrap_or("?"))?;
+        write!(f, "part two: {}", self.part_two.as_deref().unwrap_or("?"))
+    }
+}
+
+/// Finds the first example block and the emphasized example answer of each part in a puzzle description.
+pub fn extract_example(html: &str) -> Result<Example> {
+    let articles: Vec<&str> = between(html, "<article", "</article>").collect();
+    let parts = if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    };
+
+    let input = parts
+        .iter()
+        .find_map(|part| between(part, "<pre><code>", "</code></pre>").next())
+        .map(|block| unescape(&strip_tags(block)).trim_end().to_owned())
+        .ok_or_else(|| AOCError::invalid_input("No <pre><code> example block found"))?;
+
+    let answer_of = |index: usize| parts.get(index).and_then(|part| emphasized_answer(part));
+    Ok(Example {
+        input,
+        part_one: answer_of(0),
+        part_two: answer_of(1),
+    })
+}
+
+pub fn extract_example_from_file(path: &Path) -> Result<Example> {
+    extract_example(&fs::read_to_string(path)?)
+}
+
+/// Writes the example input next to the puzzle input and records its answers. An existing input
+/// or answer with the same name is only replaced when `force` is set.
+pub fn write_example(
+    example: &Example,
+    day: &str,
+    name: &str,
+    answers: &mut ExpectedAnswers,
+    force: bool,
+) -> Result<()> {
+    let dir = Path::new(day).join("input");
+    let path = dir.join(format!("{}.input", name));
+    let recorded = answers.inputs_for(day).any(|(input, _)| input == name);
+    if !force && (path.exists() || recorded) {
+        return Err(AOCError::new(format!(
+            "Example '{}' already exists for {}, use --force to overwrite it",
+            name, day
+        )));
+    }
+    fs::create_dir_all(&dir)?;
+    fs::write(path, &example.input)?;
+
+    answers.set(
+        day,
+        name,
+        Expected {
+            part_one: example.part_one.as_deref().map(Answer::from),
+            part_two: example.part_two.as_deref().map(Answer::from),
+        },
+    );
+    answers.save()
+}
+
+fn emphasized_answer(html: &str) -> Option<String> {
+    between(html, "<code><em>", "</em></code>")
+        .chain(between(html, "<em><code>", "</code></em>"))
+        .last()
+        .map(|answer| unescape(&strip_tags(answer)))
+}
+
+fn between<'a>(html: &'a str, open: &'a str, close: &'a str) -> impl Iterator<Item = &'a str> {
+    let mut rest = html;
+    std::iter::from_fn(move || {
+        let start = rest.find(open)? + open.len();
+        let end = start + rest[start..].find(close)?;
+        let found = &rest[start..end];
+        rest = &rest[end + close.len()..];
+        Some(found)
+    })
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut in_tag = false;
+    html.chars()
+        .filter(|&c| match c {
+            '<' => {
+                in_tag = true;
+                false
+            }
+            '>' if in_tag => {
+                in_tag = false;
+                false
+            }
+            _ => !in_tag,
+        })
+        .collect()
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn example_is_extracted_from_description() {
+        let example = extract_example(include_str!("../fixtures/day_01.html")).unwrap();
+
+        assert_eq!(
+            example.input,
+            "199\n200\n208\n210\n200\n207\n240\n269\n260\n263"
+        );
+        assert_eq!(example.part_one, Some("7".to_owned()));
+        assert_eq!(example.part_two, Some("5".to_owned()));
+    }
+
+    #[test]
+    fn example_is_unescaped() {
+        let example = extract_example(
+            "<article><pre><code>[&lt;&gt;({}){}[([])&lt;&gt;]]\n<em>&amp;</em></code></pre>\
+            <code><em>26397</em></code></article>",
+        )
+        .unwrap();
+
+        assert_eq!(example.input, "[<>({}){}[([])<>]]\n&");
+        assert_eq!(example.part_one, Some("26397".to_owned()));
+        assert_eq!(example.part_two, None);
+    }
+
+    #[test]
+    fn existing_example_is_kept_without_force() {
+        let day = env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        let day = day.to_str().unwrap();
+        let mut answers = ExpectedAnswers::load(&Path::new(day).join("answers.toml")).unwrap();
+        let example = |input: &str| Example {
+            input: input.to_owned(),
+            part_one: Some("1".to_owned()),
+            part_two: None,
+        };
+        let written = || fs::read_to_string(Path::new(day).join("input/test.input")).unwrap();
+
+        write_example(&example("first"), day, "test", &mut answers, false).unwrap();
+        assert!(write_example(&example("second"), day, "test", &mut answers, false).is_err());
+        assert_eq!(written(), "first");
+
+        write_example(&example("second"), day, "test", &mut answers, true).unwrap();
+        assert_eq!(written(), "second");
+        fs::remove_dir_all(day).unwrap();
+    }
+
+    #[test]
+    fn missing_example_is_reported() {
+        assert!(extract_example("<article><p>No example here</p></article>").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::client::Client;
+use crate::results::Result;
+use std::fs;
+use std::path::Path;
+use tracing::info;
+
+impl Client {
+    pub fn fetch_input(&self, day: u8) -> Result<String> {
+        let url = self.day_url(day, "input");
+        info!("Downloading input for day {} from {}", day, url);
+        self.get(&url)
+    }
+
+    /// Downloads the input for `day` to `path`, unless it was already cached there.
+    pub fn ensure_input(&self, day: u8, path: &Path) -> Result<()> {
+        if path.exists() {
+            return Ok(());
+        }
+
+        let input = self.fetch_input(day)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, input)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::results::EXIT_IO;
+    use crate::stub::StubServer;
+    use std::env;
+
+    #[test]
+    fn input_is_downloaded_with_session_cookie() {
+        let server = StubServer::serve(vec![(200, "1\n2\n3\n")]);
+        let client = Client::new("abc123".to_owned()).with_base_url(&server.url);
+
+        assert_eq!(client.fetch_input(1), Ok("1\n2\n3\n".to_owned()));
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /2021/day/1/input "));
+        assert!(requests[0].contains("session=abc123"));
+    }
+
+    #[test]
+    fn cached_input_is_not_downloaded_again() {
+        let path = env::temp_dir()
+            .join(format!("aoc-fetch-{}", std::process::id()))
+            .join("puzzle.input");
+        let server = StubServer::serve(vec![(200, "16,1,2")]);
+        let client = Client::new("abc123".to_owned())
+            .with_base_url(&server.url)
+            .with_year(2020);
+
+        assert_eq!(client.ensure_input(7, &path), Ok(()));
+        assert_eq!(client.ensure_input(7, &path), Ok(()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "16,1,2");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2020/day/7/input "));
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn failed_download_is_reported() {
+        let server = StubServer::serve(vec![(400, "Puzzle inputs differ by user.")]);
+        let client = Client::new("expired".to_owned()).with_base_url(&server.url);
+
+        let error = client.fetch_input(3).unwrap_err();
+        assert_eq!(error.kind(), "other");
+        server.requests();
+    }
+
+    #[test]
+    fn unreachable_site_is_an_io_error() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let client = Client::new("abc123".to_owned()).with_base_url(&url);
+
+        let error = client.fetch_input(3).unwrap_err();
+        assert_eq!(error.exit_code(), EXIT_IO);
+    }
+}
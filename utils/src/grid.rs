@@ -0,0 +1,308 @@
+use crate::results::AOCError;
+use crate::results::LineError;
+use crate::results::Result;
+use std::fmt::Display;
+
+/// A `(x, y)` position in a grid, with `(0, 0)` the top left cell.
+pub type Position = (usize, usize);
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const DIAGONAL: [(isize, isize); 4] = [(1, -1), (1, 1), (-1, 1), (-1, -1)];
+/// Largest number of cells [`Grid::filled`] allocates, far beyond any puzzle input.
+pub const MAX_CELLS: usize = 1 << 28;
+
+/// A dense, row-major 2-D grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, which must all have the same length.
+    pub fn new(rows: Vec<Vec<T>>) -> Result<Grid<T>> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if let Some((y, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(AOCError::InvalidInput(format!(
+                "Row {} has {} cells, expected {}",
+                y + 1,
+                row.len(),
+                width
+            )));
+        }
+        let height = rows.len();
+        Ok(Grid {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    /// Builds a grid with every cell set to `value`, refusing sizes above [`MAX_CELLS`].
+    pub fn filled(width: usize, height: usize, value: T) -> Result<Grid<T>>
+    where
+        T: Clone,
+    {
+        let size = width
+            .checked_mul(height)
+            .filter(|&size| size <= MAX_CELLS)
+            .ok_or_else(|| {
+                AOCError::InvalidInput(format!(
+                    "A grid of {}x{} cells exceeds the limit of {} cells",
+                    width, height, MAX_CELLS
+                ))
+            })?;
+        Ok(Grid {
+            cells: vec![value; size],
+            width,
+            height,
+        })
+    }
+
+    /// Parses one cell per character, reporting every character that fails to parse.
+    pub fn parse_with<F>(input: &str, parse: F) -> Result<Grid<T>>
+    where
+        F: Fn(char) -> Result<T>,
+    {
+        let mut errors = Vec::new();
+        let rows = input
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter_map(|(x, c)| match parse(c) {
+                        Ok(cell) => Some(cell),
+                        Err(error) => {
+                            errors.push(LineError {
+                                line_number: y + 1,
+                                line: line.to_owned(),
+                                column: Some(x + 1),
+                                error: Box::new(error),
+                            });
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        if errors.is_empty() {
+            Grid::new(rows)
+        } else {
+            Err(AOCError::ParseLines { path: None, errors })
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index_of(x, y).map(move |index| &mut self.cells[index])
+    }
+
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |index| (index % width, index / width))
+    }
+
+    /// Every cell together with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        self.rows().nth(y)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// The up to four cells directly above, right of, below and left of a position.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (Position, &T)> {
+        self.offsets(x, y, ORTHOGONAL.iter())
+    }
+
+    /// The up to eight cells surrounding a position, including the diagonals.
+    pub fn neighbours_with_diagonals(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        self.offsets(x, y, ORTHOGONAL.iter().chain(DIAGONAL.iter()))
+    }
+
+    fn offsets<'a, I>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: I,
+    ) -> impl Iterator<Item = (Position, &'a T)>
+    where
+        I: Iterator<Item = &'a (isize, isize)> + 'a,
+    {
+        offsets.filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+            self.get(nx, ny).map(|cell| ((nx, ny), cell))
+        })
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: Fn(&T) -> U,
+    {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: From<u8>> Grid<T> {
+    /// Parses a matrix of single decimal digits.
+    pub fn parse_digits(input: &str) -> Result<Grid<T>> {
+        Grid::parse_with(input, |c| {
+            c.to_digit(10)
+                .map(|digit| T::from(digit as u8))
+                .ok_or_else(|| AOCError::parse(&c.to_string(), "not a digit"))
+        })
+    }
+}
+
+impl Grid<char> {
+    pub fn parse_chars(input: &str) -> Result<Grid<char>> {
+        Grid::parse_with(input, Ok)
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    const DIGITS: &str = "123\n456\n789\n";
+
+    #[test]
+    fn grid_is_parsed() {
+        let grid: Grid<u8> = Grid::parse_digits(DIGITS).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get(0, 0), Some(&1));
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        assert_eq!(grid.to_string(), DIGITS.trim_end());
+
+        let chars = Grid::parse_chars("#.\n.#").unwrap();
+        assert_eq!(chars.row(1), Some(&['.', '#'][..]));
+    }
+
+    #[test]
+    fn invalid_cells_are_located() {
+        match Grid::<u8>::parse_digits("123\n4x6\n78") {
+            Err(AOCError::ParseLines { errors, .. }) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].line_number, 2);
+                assert_eq!(errors[0].column, Some(2));
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+        assert!(matches!(
+            Grid::<u8>::parse_digits("123\n45"),
+            Err(AOCError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid: Grid<u8> = Grid::parse_digits(DIGITS).unwrap();
+
+        let corner: Vec<u8> = grid.neighbours(0, 0).map(|(_, &v)| v).collect();
+        assert_eq!(corner, vec![2, 4]);
+        let centre: Vec<u8> = grid.neighbours(1, 1).map(|(_, &v)| v).collect();
+        assert_eq!(centre, vec![2, 6, 8, 4]);
+        let all: Vec<u8> = grid
+            .neighbours_with_diagonals(1, 1)
+            .map(|(_, &v)| v)
+            .collect();
+        assert_eq!(all, vec![2, 6, 8, 4, 3, 9, 7, 1]);
+        assert_eq!(grid.neighbours_with_diagonals(2, 2).count(), 3);
+    }
+
+    #[test]
+    fn rows_and_columns_are_iterated() {
+        let grid: Grid<u8> = Grid::parse_digits(DIGITS).unwrap();
+
+        let column: Vec<u8> = grid.column(1).copied().collect();
+        assert_eq!(column, vec![2, 5, 8]);
+        let sums: Vec<u8> = grid.columns().map(|c| c.sum()).collect();
+        assert_eq!(sums, vec![12, 15, 18]);
+        let sums: Vec<u8> = grid.rows().map(|r| r.iter().sum()).collect();
+        assert_eq!(sums, vec![6, 15, 24]);
+        assert_eq!(grid.iter().nth(5), Some(((2, 1), &6)));
+    }
+
+    #[test]
+    fn filled_grids_are_bounded() {
+        let grid = Grid::filled(3, 2, 0u8).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.iter().count(), 6);
+
+        assert!(matches!(
+            Grid::filled(usize::MAX, 2, 0u8),
+            Err(AOCError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            Grid::filled(MAX_CELLS, 2, 0u8),
+            Err(AOCError::InvalidInput(_))
+        ));
+    }
+}
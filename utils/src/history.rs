@@ -0,0 +1,328 @@
+use crate::bench::median;
+use crate::results::AOCError;
+use crate::results::Result;
+use crate::solution::Outcome;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+use std::time::UNIX_EPOCH;
+
+/// Number of earlier runs the rolling median of a part's timing is taken over.
+pub const ROLLING_WINDOW: usize = 10;
+/// Earlier runs needed before a slowdown is reported at all.
+const MIN_SAMPLES: usize = 3;
+/// Slowdowns smaller than this are noise, however large they are relatively.
+const MIN_SLOWDOWN_MS: f64 = 1.0;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct PartRecord {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub answer: Option<String>,
+    pub elapsed_ms: f64,
+}
+
+/// A single run of a day, as stored in the history file.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revision: Option<String>,
+    pub day: u8,
+    pub input: String,
+    pub read_ms: f64,
+    pub parse_ms: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_one: Option<PartRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part_two: Option<PartRecord>,
+}
+
+impl RunRecord {
+    pub fn new(
+        day: u8,
+        input: &str,
+        outcome: &Outcome,
+        revision: Option<String>,
+        timestamp: u64,
+    ) -> RunRecord {
+        let part = |result: &Option<Result<String>>, elapsed: Option<Duration>| {
+            elapsed.map(|elapsed| PartRecord {
+                answer: result
+                    .as_ref()
+                    .and_then(|result| result.as_ref().ok().cloned()),
+                elapsed_ms: millis(elapsed),
+            })
+        };
+        RunRecord {
+            timestamp,
+            revision,
+            day,
+            input: input.to_owned(),
+            read_ms: millis(outcome.elapsed.read),
+            parse_ms: millis(outcome.elapsed.parse),
+            part_one: part(&outcome.part_one, outcome.elapsed.part_one),
+            part_two: part(&outcome.part_two, outcome.elapsed.part_two),
+        }
+    }
+
+    pub fn parts(&self) -> [(&'static str, Option<&PartRecord>); 2] {
+        [
+            ("one", self.part_one.as_ref()),
+            ("two", self.part_two.as_ref()),
+        ]
+    }
+
+    fn same_run_as(&self, other: &RunRecord) -> bool {
+        self.day == other.day && self.input == other.input
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1e3
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Warning {
+    AnswerChanged {
+        part: &'static str,
+        previous: String,
+        current: String,
+    },
+    Slower {
+        part: &'static str,
+        median_ms: f64,
+        current_ms: f64,
+    },
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Warning::AnswerChanged {
+                part,
+                previous,
+                current,
+            } => write!(
+                f,
+                "answer to part {} changed from {} to {}",
+                part, previous, current
+            ),
+            Warning::Slower {
+                part,
+                median_ms,
+                current_ms,
+            } => write!(
+                f,
+                "part {} took {:.1} ms, {:.1}x the rolling median of {:.1} ms",
+                part,
+                current_ms,
+                current_ms / median_ms,
+                median_ms
+            ),
+        }
+    }
+}
+
+/// Every recorded run, backed by a JSON lines file.
+#[derive(Debug)]
+pub struct History {
+    path: PathBuf,
+    records: Vec<RunRecord>,
+}
+
+impl History {
+    pub fn load(path: &Path) -> Result<History> {
+        let records = if path.exists() {
+            fs::read_to_string(path)?
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|line| {
+                    serde_json::from_str(line).map_err(|err| {
+                        AOCError::parse_with_source(&path.display().to_string(), err)
+                    })
+                })
+                .collect::<Result<_>>()?
+        } else {
+            Vec::new()
+        };
+        Ok(History {
+            path: path.to_path_buf(),
+            records,
+        })
+    }
+
+    pub fn records(&self) -> &[RunRecord] {
+        &self.records
+    }
+
+    /// Compares a run with the earlier runs of the same day and input.
+    ///
+    /// Warns when an answer differs from the last known one, or when a part is more than
+    /// `slowdown` times slower than the median of the last [`ROLLING_WINDOW`] runs.
+    pub fn check(&self, record: &RunRecord, slowdown: f64) -> Vec<Warning> {
+        let earlier: Vec<&RunRecord> = self
+            .records
+            .iter()
+            .filter(|earlier| earlier.same_run_as(record))
+            .collect();
+
+        let mut warnings = Vec::new();
+        for (index, (part, current)) in record.parts().into_iter().enumerate() {
+            let current = match current {
+                Some(current) => current,
+                None => continue,
+            };
+            let previous: Vec<&PartRecord> = earlier
+                .iter()
+                .filter_map(|earlier| earlier.parts()[index].1)
+                .collect();
+
+            let last_answer = previous.iter().rev().find_map(|p| p.answer.as_ref());
+            if let (Some(previous), Some(current)) = (last_answer, &current.answer) {
+                if previous != current {
+                    warnings.push(Warning::AnswerChanged {
+                        part,
+                        previous: previous.clone(),
+                        current: current.clone(),
+                    });
+                }
+            }
+
+            let window: Vec<f64> = previous
+                .iter()
+                .rev()
+                .take(ROLLING_WINDOW)
+                .map(|p| p.elapsed_ms)
+                .collect();
+            if let Some(median_ms) = median(window).filter(|_| previous.len() >= MIN_SAMPLES) {
+                let current_ms = current.elapsed_ms;
+                if current_ms > median_ms * slowdown && current_ms - median_ms > MIN_SLOWDOWN_MS {
+                    warnings.push(Warning::Slower {
+                        part,
+                        median_ms,
+                        current_ms,
+                    });
+                }
+            }
+        }
+        warnings
+    }
+
+    pub fn append(&mut self, record: RunRecord) -> Result<()> {
+        let line = serde_json::to_string(&record)
+            .map_err(|err| AOCError::new(format!("Failed to serialize run: {}", err)))?;
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", line)?;
+        self.records.push(record);
+        Ok(())
+    }
+}
+
+pub fn format_timestamp(timestamp: u64) -> String {
+    humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(timestamp)).to_string()
+}
+
+/// The checked out git revision, marked `-dirty` when there are local changes.
+pub fn git_revision() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let revision = String::from_utf8(output.stdout).ok()?;
+    Some(revision.trim().to_owned()).filter(|revision| !revision.is_empty())
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn run(part_one: &str, elapsed_ms: f64) -> RunRecord {
+        RunRecord {
+            timestamp: 0,
+            revision: None,
+            day: 1,
+            input: "puzzle".to_owned(),
+            read_ms: 0.1,
+            parse_ms: 0.2,
+            part_one: Some(PartRecord {
+                answer: Some(part_one.to_owned()),
+                elapsed_ms,
+            }),
+            part_two: None,
+        }
+    }
+
+    fn history(records: Vec<RunRecord>) -> History {
+        History {
+            path: PathBuf::new(),
+            records,
+        }
+    }
+
+    #[test]
+    fn changed_answers_are_reported() {
+        let history = history(vec![run("7", 2.0), run("8", 2.0)]);
+
+        assert_eq!(history.check(&run("8", 2.0), 1.5), vec![]);
+        assert_eq!(
+            history.check(&run("9", 2.0), 1.5),
+            vec![Warning::AnswerChanged {
+                part: "one",
+                previous: "8".to_owned(),
+                current: "9".to_owned(),
+            }]
+        );
+
+        let mut other_input = run("9", 2.0);
+        other_input.input = "test".to_owned();
+        assert_eq!(history.check(&other_input, 1.5), vec![]);
+    }
+
+    #[test]
+    fn slowdowns_are_measured_against_the_rolling_median() {
+        let history = history(vec![
+            run("7", 100.0),
+            run("7", 10.0),
+            run("7", 12.0),
+            run("7", 11.0),
+        ]);
+
+        assert_eq!(history.check(&run("7", 15.0), 1.5), vec![]);
+        assert_eq!(
+            history.check(&run("7", 30.0), 1.5),
+            vec![Warning::Slower {
+                part: "one",
+                median_ms: 11.5,
+                current_ms: 30.0,
+            }]
+        );
+        assert_eq!(
+            self::history(vec![run("7", 0.1); 3]).check(&run("7", 0.5), 1.5),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn records_are_serialized_as_json_lines() {
+        let record = run("7", 2.0);
+        let line = serde_json::to_string(&record).unwrap();
+
+        assert!(!line.contains('\n'));
+        assert!(!line.contains("revision"));
+        assert_eq!(serde_json::from_str::<RunRecord>(&line).unwrap(), record);
+    }
+}
@@ -1,55 +1,586 @@
 use crate::results::AOCError;
+use crate::results::LineError;
 use crate::results::Result;
-use itertools::Itertools;
-use std::env;
 use std::error::Error;
 use std::fmt::Debug;
+use std::fmt::Display;
 use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
 use std::path::Path;
+use std::path::PathBuf;
 use std::str::FromStr;
 
-pub fn parse_day(main_file: &str) -> &str {
-    main_file.trim_end_matches("/src/main.rs")
+pub fn day_dir(day: u8) -> String {
+    format!("day_{:02}", day)
 }
 
-pub fn file_name_from_args() -> String {
-    env::args().nth(1).unwrap_or_else(|| "puzzle".to_owned())
+pub fn input_path(day: &str, file_name: &str) -> PathBuf {
+    Path::new(day)
+        .join("input")
+        .join(format!("{}.input", file_name))
 }
 
 pub fn path_for_day(day: &str, file_name: &str) -> Result<Box<Path>> {
-    let path = fs::canonicalize(Path::new(&format!("{}/input/{}.input", day, file_name)))?;
-    Ok(path.into_boxed_path())
+    existing_path(&input_path(day, file_name))
+}
+
+fn existing_path(path: &Path) -> Result<Box<Path>> {
+    if !path.exists() {
+        return Err(AOCError::Io(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Input file '{}' does not exist", path.display()),
+        )));
+    }
+    Ok(fs::canonicalize(path)?.into_boxed_path())
+}
+
+/// Where the puzzle input is read from.
+#[derive(Debug, PartialEq, Clone)]
+pub enum InputSource {
+    /// A file `<day>/input/<name>.input`
+    Named(String),
+    Path(PathBuf),
+    Stdin,
+    Literal(String),
+}
+
+impl InputSource {
+    /// Resolves the path the input is read from, if it comes from a file.
+    pub fn path(&self, day: &str) -> Result<Option<Box<Path>>> {
+        match self {
+            InputSource::Named(name) => path_for_day(day, name).map(Some),
+            InputSource::Path(path) => existing_path(path).map(Some),
+            InputSource::Stdin | InputSource::Literal(_) => Ok(None),
+        }
+    }
+
+    pub fn read(&self, day: &str) -> Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::Literal(input) => Ok(input.clone()),
+            source => match source.path(day)? {
+                Some(path) => read_string_from_file(&path),
+                None => Ok(String::new()),
+            },
+        }
+    }
+
+    /// Opens the input for reading without loading it into memory first.
+    pub fn open(&self, day: &str) -> Result<Box<dyn BufRead + '_>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::Literal(input) => Ok(Box::new(input.as_bytes())),
+            source => match source.path(day)? {
+                Some(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+                None => Ok(Box::new(io::empty())),
+            },
+        }
+    }
+
+    /// Reads stdin up front so the same input can be handed to several days.
+    pub fn buffered(self) -> Result<InputSource> {
+        match self {
+            InputSource::Stdin => InputSource::Stdin.read("").map(InputSource::Literal),
+            source => Ok(source),
+        }
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = AOCError;
+
+    /// `-` reads stdin, anything with a path separator or an extension is a file path
+    /// and everything else names a file in the day's input directory.
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        let path = Path::new(input);
+        if input == "-" {
+            Ok(InputSource::Stdin)
+        } else if path.components().count() > 1 || path.extension().is_some() {
+            Ok(InputSource::Path(path.to_path_buf()))
+        } else if input.is_empty() {
+            Err(AOCError::parse(input, "empty input name"))
+        } else {
+            Ok(InputSource::Named(input.to_owned()))
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            InputSource::Named(name) => write!(f, "{}", name),
+            InputSource::Path(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Literal(_) => write!(f, "literal"),
+        }
+    }
 }
 
 pub fn read_string_from_file(path: &Path) -> Result<String> {
     fs::read_to_string(path).map_err(AOCError::from)
 }
 
+pub fn read_lines<C: FromIterator<String>>(input: &str) -> C {
+    input.lines().map(|line| line.to_string()).collect()
+}
+
 pub fn read_lines_from_file<C: FromIterator<String>>(path: &Path) -> Result<C> {
-    read_string_from_file(path)
-        .map(|contents| contents.lines().map(|line| line.to_string()).collect())
+    read_string_from_file(path).map(|contents| read_lines(&contents))
 }
 
-pub fn parse_lines_from_file<C, I>(path: &Path) -> Result<C>
+pub fn parse_lines<C, I>(input: &str) -> Result<C>
 where
     I: FromStr + Debug,
     C: FromIterator<I>,
-    I::Err: Error,
+    I::Err: Error + Send + Sync + 'static,
 {
-    let contents = fs::read_to_string(path)?;
-    contents
+    let mut errors = Vec::new();
+    let parsed: C = input
         .lines()
-        .map(|line| line.parse::<I>())
-        .try_collect()
-        .map_err(|err| AOCError::new(err.to_string()))
+        .enumerate()
+        .filter_map(|(index, line)| match line.parse::<I>() {
+            Ok(item) => Some(item),
+            Err(err) => {
+                errors.push(LineError::from_error(index + 1, line, err));
+                None
+            }
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(AOCError::ParseLines { path: None, errors })
+    }
 }
 
-pub fn parse_string_from_file<I>(path: &Path) -> Result<I>
+pub fn parse_lines_from_file<C, I>(path: &Path) -> Result<C>
+where
+    I: FromStr + Debug,
+    C: FromIterator<I>,
+    I::Err: Error + Send + Sync + 'static,
+{
+    parse_lines(&read_string_from_file(path)?).map_err(|err| err.with_path(path))
+}
+
+/// Lazily parses every line of a reader, so arbitrarily large inputs are processed in constant memory.
+pub fn parse_lines_iter<I, R>(reader: R) -> impl Iterator<Item = Result<I>>
+where
+    R: BufRead,
+    I: FromStr,
+    I::Err: Error + Send + Sync + 'static,
+{
+    reader.lines().enumerate().map(|(index, line)| {
+        let line = line?;
+        line.parse::<I>().map_err(|err| AOCError::ParseLines {
+            path: None,
+            errors: vec![LineError::from_error(index + 1, &line, err)],
+        })
+    })
+}
+
+pub fn parse_lines_iter_from_file<I>(path: &Path) -> Result<impl Iterator<Item = Result<I>>>
+where
+    I: FromStr,
+    I::Err: Error + Send + Sync + 'static,
+{
+    let reader = BufReader::new(File::open(path)?);
+    let path = path.to_path_buf();
+    Ok(parse_lines_iter(reader).map(move |item| item.map_err(|err| err.with_path(&path))))
+}
+
+/// Parses a list of values separated by `separator`, ignoring surrounding whitespace and a trailing
+/// separator. A whitespace separator splits on any run of whitespace.
+///
+/// Input without any value is rejected, as it is never a valid list of puzzle values.
+pub fn parse_delimited<C, I>(input: &str, separator: char) -> Result<C>
+where
+    I: FromStr,
+    C: FromIterator<I>,
+    I::Err: Error + Send + Sync + 'static,
+{
+    let whitespace = separator.is_whitespace();
+    let is_separator = |c: char| c == separator || (whitespace && c.is_whitespace());
+
+    let mut offset = 0;
+    let pieces: Vec<(usize, &str)> = input
+        .split_inclusive(is_separator)
+        .map(|piece| {
+            let start = offset;
+            offset += piece.len();
+            (start, piece)
+        })
+        .collect();
+    let last = pieces.len().saturating_sub(1);
+
+    let mut errors = Vec::new();
+    let mut count = 0;
+    let parsed: C = pieces
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, (start, piece))| {
+            let item = piece.trim_end_matches(is_separator);
+            let value = item.trim();
+            let position = start + item.len() - item.trim_start().len();
+            let result = if value.is_empty() {
+                if whitespace || index == last {
+                    return None;
+                }
+                Err(AOCError::parse(value, "empty value"))
+            } else {
+                value
+                    .parse::<I>()
+                    .map_err(|err| AOCError::parse_with_source(value, err))
+            };
+            count += 1;
+            result
+                .map_err(|error| errors.push(located_error(input, position, error)))
+                .ok()
+        })
+        .collect();
+
+    if count == 0 {
+        let error = AOCError::parse(input.trim(), "expected at least one value");
+        errors.push(located_error(input, 0, error));
+    }
+    if errors.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(AOCError::ParseLines { path: None, errors })
+    }
+}
+
+pub(crate) fn located_error(input: &str, position: usize, error: AOCError) -> LineError {
+    let line_start = input[..position].rfind('\n').map_or(0, |index| index + 1);
+    LineError {
+        line_number: input[..position].matches('\n').count() + 1,
+        line: input[line_start..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_owned(),
+        column: Some(input[line_start..position].chars().count() + 1),
+        error: Box::new(error),
+    }
+}
+
+/// A block of consecutive non-blank lines.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Section<'a> {
+    /// Position of the section in the input, starting at 1
+    pub number: usize,
+    /// Line number of the first line of the section in the input
+    pub first_line: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.text.lines()
+    }
+
+    pub fn parse<I>(&self) -> Result<I>
+    where
+        I: FromStr,
+        I::Err: Error + Send + Sync + 'static,
+    {
+        self.parse_with(|text| {
+            text.parse::<I>()
+                .map_err(|err| AOCError::from_parse_error(text, err))
+        })
+    }
+
+    /// Parses the section with a closure, attributing any error to this section and, for errors
+    /// that carry line numbers such as those of [`parse_lines`], to the line within it that failed.
+    pub fn parse_with<T, F>(&self, parse: F) -> Result<T>
+    where
+        F: FnOnce(&'a str) -> Result<T>,
+    {
+        parse(self.text).map_err(|error| AOCError::Section {
+            number: self.number,
+            error: Box::new(self.locate(error)),
+        })
+    }
+
+    fn locate(&self, error: AOCError) -> AOCError {
+        match error {
+            AOCError::ParseLines { path, errors } => AOCError::ParseLines {
+                path,
+                errors: errors
+                    .into_iter()
+                    .map(|error| LineError {
+                        line_number: error.line_number + self.first_line - 1,
+                        ..error
+                    })
+                    .collect(),
+            },
+            other => other,
+        }
+    }
+}
+
+/// Splits the input into blocks separated by one or more blank lines.
+pub fn sections(input: &str) -> impl Iterator<Item = Section<'_>> {
+    let mut offset = 0;
+    let mut lines = input
+        .split_inclusive('\n')
+        .enumerate()
+        .map(move |(index, line)| {
+            let start = offset;
+            offset += line.len();
+            (index + 1, start, line)
+        })
+        .peekable();
+    let is_blank = |(_, _, line): &(usize, usize, &str)| line.trim().is_empty();
+
+    let mut number = 0;
+    std::iter::from_fn(move || {
+        while lines.next_if(is_blank).is_some() {}
+        let (first_line, start, line) = lines.next()?;
+        let mut end = start + line.len();
+        while let Some((_, line_start, line)) = lines.next_if(|line| !is_blank(line)) {
+            end = line_start + line.len();
+        }
+        number += 1;
+        Some(Section {
+            number,
+            first_line,
+            text: input[start..end].trim_end_matches(['\n', '\r']),
+        })
+    })
+}
+
+pub fn parse_string<I>(input: &str) -> Result<I>
 where
     I: FromStr + Debug,
-    I::Err: Error,
+    I::Err: Error + Send + Sync + 'static,
 {
-    read_string_from_file(path)?
+    input
         .parse::<I>()
-        .map_err(|err| AOCError::new(err.to_string()))
+        .map_err(|err| AOCError::parse_with_source(input, err))
+}
+
+pub fn parse_string_from_file<I>(path: &Path) -> Result<I>
+where
+    I: FromStr + Debug,
+    I::Err: Error + Send + Sync + 'static,
+{
+    parse_string(&read_string_from_file(path)?)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use itertools::Itertools;
+
+    #[derive(Debug, PartialEq)]
+    struct Pair(u8, u8);
+
+    impl FromStr for Pair {
+        type Err = AOCError;
+
+        fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+            let (a, b) = input
+                .split_once(',')
+                .ok_or_else(|| AOCError::parse(input, "expected a comma"))?;
+            let parse = |s: &str| s.parse().map_err(|e| AOCError::parse_with_source(s, e));
+            Ok(Pair(parse(a)?, parse(b)?))
+        }
+    }
+
+    #[test]
+    fn input_files_are_read_from_paths() {
+        let path = std::env::temp_dir().join(format!("generated-{}", std::process::id()));
+        fs::write(&path, "1,2,3").unwrap();
+        let source = path.to_str().unwrap().parse::<InputSource>();
+        let read = InputSource::Path(path.clone()).read("day_06");
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(source, Ok(InputSource::Path(path)));
+        assert_eq!(read, Ok("1,2,3".to_owned()));
+    }
+
+    #[test]
+    fn input_sources_are_recognised() {
+        assert_eq!("-".parse(), Ok(InputSource::Stdin));
+        assert_eq!("test".parse(), Ok(InputSource::Named("test".to_owned())));
+        assert_eq!(
+            "inputs/big.txt".parse(),
+            Ok(InputSource::Path(PathBuf::from("inputs/big.txt")))
+        );
+        assert_eq!(
+            "/tmp/generated".parse(),
+            Ok(InputSource::Path(PathBuf::from("/tmp/generated")))
+        );
+        assert_eq!(
+            InputSource::Literal("1,2,3".to_owned()).read("day_06"),
+            Ok("1,2,3".to_owned())
+        );
+    }
+
+    #[test]
+    fn lines_are_parsed() {
+        let pairs: Vec<Pair> = parse_lines("1,2\n3,4").unwrap();
+        assert_eq!(pairs, vec![Pair(1, 2), Pair(3, 4)]);
+    }
+
+    #[test]
+    fn every_failing_line_is_reported() {
+        let result: Result<Vec<Pair>> = parse_lines("1,2\n3,x4\n5;6\n7,8");
+
+        match result {
+            Err(AOCError::ParseLines { path: None, errors }) => {
+                assert_eq!(errors.len(), 2);
+                assert_eq!(errors[0].line_number, 2);
+                assert_eq!(errors[0].line, "3,x4");
+                assert_eq!(errors[0].column, Some(3));
+                assert_eq!(errors[1].line_number, 3);
+                assert_eq!(errors[1].column, Some(1));
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn lines_are_streamed() {
+        let mut pairs = parse_lines_iter::<Pair, _>(io::Cursor::new("1,2\n3;4\n5,6\n"));
+
+        assert_eq!(pairs.next(), Some(Ok(Pair(1, 2))));
+        match pairs.next() {
+            Some(Err(AOCError::ParseLines { errors, .. })) => {
+                assert_eq!(errors.len(), 1);
+                assert_eq!(errors[0].line_number, 2);
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+        assert_eq!(pairs.next(), Some(Ok(Pair(5, 6))));
+        assert_eq!(pairs.next(), None);
+    }
+
+    #[test]
+    fn delimited_values_are_parsed() {
+        assert_eq!(parse_delimited("3,4,3,1,2", ','), Ok(vec![3, 4, 3, 1, 2]));
+        assert_eq!(parse_delimited(" 3, 4 ,3,\n", ','), Ok(vec![3, 4, 3]));
+        assert_eq!(
+            parse_delimited("16 1\n  2  0\n", ' '),
+            Ok(vec![16, 1, 2, 0])
+        );
+        assert_eq!(parse_delimited("1;2;3", ';'), Ok(vec![1u8, 2, 3]));
+    }
+
+    #[test]
+    fn empty_delimited_input_is_rejected() {
+        for input in ["", " \n", ",", "\n\n"] {
+            match parse_delimited::<Vec<u8>, u8>(input, ',') {
+                Err(AOCError::ParseLines { errors, .. }) => {
+                    assert_eq!(errors.len(), 1);
+                    assert_eq!((errors[0].line_number, errors[0].column), (1, Some(1)));
+                }
+                other => panic!("Unexpected result {:?} for {:?}", other, input),
+            }
+        }
+        assert!(parse_delimited::<Vec<u8>, u8>("  ", ' ').is_err());
+    }
+
+    #[test]
+    fn delimited_errors_are_located() {
+        match parse_delimited::<Vec<u8>, u8>("1,2,,x3,\n4, 300", ',') {
+            Err(AOCError::ParseLines { errors, .. }) => {
+                let locations: Vec<(usize, Option<usize>)> = errors
+                    .iter()
+                    .map(|error| (error.line_number, error.column))
+                    .collect();
+                assert_eq!(locations, vec![(1, Some(5)), (1, Some(6)), (2, Some(4))]);
+                assert_eq!(errors[2].line, "4, 300");
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn sections_are_split_on_blank_lines() {
+        let found: Vec<Section> = sections("\n1,2\n\n3,4\n5,6\n\n\n7,8\n").collect();
+
+        assert_eq!(
+            found,
+            vec![
+                Section {
+                    number: 1,
+                    first_line: 2,
+                    text: "1,2"
+                },
+                Section {
+                    number: 2,
+                    first_line: 4,
+                    text: "3,4\n5,6"
+                },
+                Section {
+                    number: 3,
+                    first_line: 8,
+                    text: "7,8"
+                },
+            ]
+        );
+        assert_eq!(found[0].parse(), Ok(Pair(1, 2)));
+        assert_eq!(sections("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn section_errors_are_located() {
+        let input = "1,2\n\n3,4\n5,x\n";
+        let section = sections(input).nth(1).unwrap();
+
+        let error = section
+            .parse_with(parse_lines::<Vec<Pair>, Pair>)
+            .unwrap_err();
+        match &error {
+            AOCError::Section { number: 2, error } => match error.as_ref() {
+                AOCError::ParseLines { errors, .. } => assert_eq!(errors[0].line_number, 4),
+                other => panic!("Unexpected error {:?}", other),
+            },
+            other => panic!("Unexpected error {:?}", other),
+        }
+
+        let section = sections("0,0\n\n1,2\n3,4\n1\n").nth(1).unwrap();
+        let error = section
+            .parse_with(|text| {
+                text.lines()
+                    .map(|line| line.parse::<Pair>())
+                    .try_collect::<_, Vec<_>, _>()
+            })
+            .unwrap_err();
+        assert_eq!(
+            error,
+            AOCError::Section {
+                number: 2,
+                error: Box::new(AOCError::parse("1", "expected a comma"))
+            }
+        );
+    }
+
+    #[test]
+    fn failures_are_rendered_as_diagnostics() {
+        let error = parse_lines::<Vec<Pair>, Pair>("1,2\n3,x4")
+            .unwrap_err()
+            .with_path(Path::new("day_00/input/test.input"));
+
+        assert_eq!(
+            error.to_string(),
+            "1 line(s) failed to parse\n\
+            error: Failed to parse 'x4': invalid digit found in string\n \
+            --> day_00/input/test.input:2:3\n  \
+            |\n\
+            2 | 3,x4\n  \
+            |   ^^"
+        );
+    }
 }
@@ -1,3 +1,19 @@
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod client;
+pub mod examples;
+pub mod fetch;
+pub mod grid;
+pub mod history;
 pub mod input;
 pub mod output;
+pub mod parser;
 pub mod results;
+pub mod snapshot;
+pub mod solution;
+pub mod submit;
+pub mod trace;
+
+#[cfg(test)]
+mod stub;
@@ -1,26 +1,378 @@
+use crate::results::AOCError;
 use crate::results::Result;
+use crate::solution::Elapsed;
+use crate::solution::Outcome;
+use crate::solution::Part;
 use ansi_term::Colour::Green;
 use ansi_term::Colour::Red;
 use ansi_term::Style;
-use env_logger::Builder;
-use env_logger::Env;
+use serde_json::json;
+use serde_json::Value;
+use std::env;
+use std::ffi::OsString;
 use std::fmt::Display;
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tracing::info;
+
+static STDOUT_COLOR: AtomicBool = AtomicBool::new(false);
+static STDERR_COLOR: AtomicBool = AtomicBool::new(false);
+
+/// An output stream, each of which may or may not be a terminal.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Stream {
+    /// Answers and reports.
+    Stdout,
+    /// The log, including everything rendered into it.
+    Stderr,
+}
+
+impl Stream {
+    fn color(self) -> &'static AtomicBool {
+        match self {
+            Stream::Stdout => &STDOUT_COLOR,
+            Stream::Stderr => &STDERR_COLOR,
+        }
+    }
+}
+
+/// Enables or disables colour on both streams.
+pub fn set_color(enabled: bool) {
+    STDOUT_COLOR.store(enabled, Ordering::Relaxed);
+    STDERR_COLOR.store(enabled, Ordering::Relaxed);
+}
+
+pub fn color_enabled(stream: Stream) -> bool {
+    stream.color().load(Ordering::Relaxed)
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        match input {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            unrec => Err(AOCError::parse(unrec, "expected auto, always or never")),
+        }
+    }
+}
+
+/// Enables colour for `choice`, looking at `NO_COLOR` and whether each stream is a terminal
+/// when it is `auto`.
+pub fn init_color(choice: ColorChoice) {
+    let terminals = [
+        (Stream::Stdout, std::io::stdout().is_terminal()),
+        (Stream::Stderr, std::io::stderr().is_terminal()),
+    ];
+    for (stream, is_terminal) in terminals {
+        let enabled = color_policy(choice, env::var_os("NO_COLOR"), is_terminal);
+        stream.color().store(enabled, Ordering::Relaxed);
+    }
+}
+
+fn color_policy(choice: ColorChoice, no_color: Option<OsString>, is_terminal: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => no_color.is_none_or(|value| value.is_empty()) && is_terminal,
+    }
+}
+
+/// Paints `text` for the log with `style` when colour is enabled, and leaves it plain otherwise.
+pub fn paint<T: Display>(style: Style, text: T) -> String {
+    paint_to(Stream::Stderr, style, text)
+}
+
+/// Paints `text` with `style` when colour is enabled for `stream`.
+pub fn paint_to<T: Display>(stream: Stream, style: Style, text: T) -> String {
+    if color_enabled(stream) {
+        style.paint(text.to_string()).to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Format {
+    Text,
+    /// A single JSON array with one record per part, written when the run finishes.
+    Json,
+    /// One JSON record per line, written as soon as a part is solved.
+    JsonLines,
+}
+
+impl FromStr for Format {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        match input {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "jsonl" | "json-lines" => Ok(Format::JsonLines),
+            unrec => Err(AOCError::parse(unrec, "expected text, json or jsonl")),
+        }
+    }
+}
 
 pub fn display_result<I: Display>(result: Result<I>) -> String {
     let style = Style::new().bold();
     match result {
-        Ok(outcome) => style.fg(Green).paint(format!("{}", outcome)).to_string(),
-        Err(error) => Red
-            .paint(format!(
-                "{}: {}",
-                "Failed to compute result",
-                style.paint(format!("{}", error))
-            ))
-            .to_string(),
+        Ok(outcome) => paint_to(Stream::Stdout, style.fg(Green), outcome),
+        Err(error) => paint_to(
+            Stream::Stdout,
+            Red.normal(),
+            format!(
+                "Failed to compute result: {}",
+                paint_to(Stream::Stdout, style, error)
+            ),
+        ),
+    }
+}
+
+/// Reports the outcome of every solved day in the selected format.
+pub struct Reporter {
+    format: Format,
+    records: Vec<Value>,
+}
+
+impl Reporter {
+    pub fn new(format: Format) -> Reporter {
+        Reporter {
+            format,
+            records: Vec::new(),
+        }
+    }
+
+    pub fn outcome(&mut self, day: u8, input: &str, outcome: Outcome) {
+        match self.format {
+            Format::Text => print_outcome(day, outcome),
+            _ => outcome_records(day, input, &outcome)
+                .into_iter()
+                .for_each(|record| self.emit(record)),
+        }
+    }
+
+    /// Reports a run that failed before any of its parts could be solved.
+    pub fn failure(&mut self, day: u8, input: &str, part: Part, error: &AOCError) {
+        if self.format == Format::Text {
+            return;
+        }
+        let parts = [(1, part.includes_one()), (2, part.includes_two())];
+        for (number, _) in parts.iter().filter(|(_, included)| *included) {
+            self.emit(json!({
+                "day": day,
+                "part": number,
+                "input": input,
+                "error": error_json(error),
+                "elapsed_ms": Value::Null,
+            }));
+        }
+    }
+
+    fn emit(&mut self, record: Value) {
+        match self.format {
+            Format::JsonLines => println!("{}", record),
+            _ => self.records.push(record),
+        }
+    }
+
+    /// Writes the collected records, for formats that cannot be streamed.
+    pub fn finish(self) {
+        if self.format == Format::Json {
+            println!("{:#}", Value::Array(self.records));
+        }
+    }
+}
+
+/// Prints the answers to stdout and logs the timings.
+fn print_outcome(day: u8, outcome: Outcome) {
+    let parts = [
+        ("one", outcome.part_one, outcome.elapsed.part_one),
+        ("two", outcome.part_two, outcome.elapsed.part_two),
+    ];
+    for (part, result, elapsed) in parts {
+        if let (Some(result), Some(elapsed)) = (result, elapsed) {
+            println!(
+                "Day {} part {}: {} ({})",
+                day,
+                part,
+                display_result(result),
+                display_duration(elapsed)
+            );
+        }
+    }
+    info!("{}", display_elapsed(&outcome.elapsed));
+}
+
+/// Formats a duration with a unit that keeps it readable, e.g. `12.3 ms`.
+pub fn display_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+    if seconds >= 1.0 {
+        format!("{:.2} s", seconds)
+    } else if seconds >= 1e-3 {
+        format!("{:.1} ms", seconds * 1e3)
+    } else {
+        format!("{:.1} µs", seconds * 1e6)
+    }
+}
+
+fn display_elapsed(elapsed: &Elapsed) -> String {
+    format!(
+        "Total: {} (read {}, parse {})",
+        display_duration(elapsed.total()),
+        display_duration(elapsed.read),
+        display_duration(elapsed.parse)
+    )
+}
+
+fn outcome_records(day: u8, input: &str, outcome: &Outcome) -> Vec<Value> {
+    let millis = |duration: Duration| duration.as_secs_f64() * 1e3;
+    let parts = [
+        (1, &outcome.part_one, outcome.elapsed.part_one),
+        (2, &outcome.part_two, outcome.elapsed.part_two),
+    ];
+    parts
+        .iter()
+        .filter_map(|(part, result, elapsed)| {
+            let mut record = json!({
+                "day": day,
+                "part": part,
+                "input": input,
+                "elapsed_ms": {
+                    "read": millis(outcome.elapsed.read),
+                    "parse": millis(outcome.elapsed.parse),
+                    "part": elapsed.map(millis),
+                },
+            });
+            match result.as_ref()? {
+                Ok(answer) => record["answer"] = json!(answer),
+                Err(error) => record["error"] = error_json(error),
+            }
+            Some(record)
+        })
+        .collect()
+}
+
+fn error_json(error: &AOCError) -> Value {
+    let mut value = json!({ "kind": error.kind(), "message": error.to_string() });
+    match error {
+        AOCError::ParseLines { errors, .. } => {
+            value["lines"] = errors
+                .iter()
+                .map(|error| {
+                    json!({
+                        "line": error.line_number,
+                        "column": error.column,
+                        "message": error.error.to_string(),
+                    })
+                })
+                .collect();
+        }
+        AOCError::Section { number, error } => {
+            value["section"] = json!(number);
+            value["cause"] = error_json(error);
+        }
+        _ => {}
     }
+    value
 }
 
-pub fn init_env_log() {
-    let env = Env::default().default_filter_or("info");
-    Builder::from_env(env).format_timestamp(None).init();
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::results::LineError;
+
+    #[test]
+    fn outcome_is_rendered_as_json() {
+        let outcome = Outcome {
+            part_one: Some(Ok("7".to_owned())),
+            part_two: Some(Err(AOCError::no_solution("No winner found"))),
+            elapsed: Elapsed {
+                read: Duration::from_micros(500),
+                parse: Duration::from_millis(2),
+                part_one: Some(Duration::from_millis(3)),
+                part_two: Some(Duration::from_millis(4)),
+            },
+        };
+
+        let records: Vec<String> = outcome_records(1, "test", &outcome)
+            .iter()
+            .map(Value::to_string)
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                r#"{"answer":"7","day":1,"elapsed_ms":{"parse":2.0,"part":3.0,"read":0.5},"input":"test","part":1}"#,
+                r#"{"day":1,"elapsed_ms":{"parse":2.0,"part":4.0,"read":0.5},"error":{"kind":"no_solution","message":"No solution: No winner found"},"input":"test","part":2}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_are_structured() {
+        let error = AOCError::Section {
+            number: 2,
+            error: Box::new(AOCError::ParseLines {
+                path: None,
+                errors: vec![LineError::new(
+                    3,
+                    "1 x",
+                    AOCError::parse("x", "not a number"),
+                )],
+            }),
+        };
+
+        let value = error_json(&error);
+        assert_eq!(value["kind"], "parse");
+        assert_eq!(value["section"], 2);
+        assert_eq!(value["cause"]["lines"][0]["line"], 3);
+        assert_eq!(
+            value["cause"]["lines"][0]["message"],
+            "Failed to parse 'x': not a number"
+        );
+    }
+
+    #[test]
+    fn color_follows_the_policy() {
+        let no_color = || Some(OsString::from("1"));
+
+        assert!(color_policy(ColorChoice::Auto, None, true));
+        assert!(color_policy(ColorChoice::Auto, Some(OsString::new()), true));
+        assert!(!color_policy(ColorChoice::Auto, no_color(), true));
+        assert!(!color_policy(ColorChoice::Auto, None, false));
+        assert!(color_policy(ColorChoice::Always, no_color(), false));
+        assert!(!color_policy(ColorChoice::Never, None, true));
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+
+    #[test]
+    fn durations_are_readable() {
+        assert_eq!(display_duration(Duration::from_micros(12_345)), "12.3 ms");
+        assert_eq!(display_duration(Duration::from_nanos(4_560)), "4.6 µs");
+        assert_eq!(display_duration(Duration::from_millis(2_500)), "2.50 s");
+
+        let elapsed = Elapsed {
+            read: Duration::from_millis(1),
+            parse: Duration::from_millis(2),
+            part_one: Some(Duration::from_millis(3)),
+            part_two: None,
+        };
+        assert_eq!(
+            display_elapsed(&elapsed),
+            "Total: 6.0 ms (read 1.0 ms, parse 2.0 ms)"
+        );
+    }
 }
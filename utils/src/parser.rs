@@ -0,0 +1,582 @@
+use crate::grid::Grid;
+use crate::input::located_error;
+use crate::results::AOCError;
+use crate::results::Result;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// The remaining input of a parser, remembering its position in the full text and the furthest
+/// failure that was backtracked from on the way there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Input<'a> {
+    source: &'a str,
+    position: usize,
+    furthest: Option<Rc<Failure>>,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(source: &'a str) -> Input<'a> {
+        Input {
+            source,
+            position: 0,
+            furthest: None,
+        }
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    fn advance(&self, bytes: usize) -> Input<'a> {
+        Input {
+            source: self.source,
+            position: self.position + bytes,
+            furthest: self.furthest.clone(),
+        }
+    }
+
+    /// The same position, remembering the failure of an alternative that was given up on.
+    fn backtrack(self, failure: &Failure) -> Input<'a> {
+        Input {
+            furthest: failure.furthest.clone().or(self.furthest),
+            ..self
+        }
+    }
+
+    fn failure(&self, expected: &str) -> Failure {
+        let failure = Failure {
+            position: self.position,
+            expected: expected.to_owned(),
+            context: Vec::new(),
+            fatal: false,
+            furthest: None,
+        };
+        let furthest = match &self.furthest {
+            Some(best) if best.position > failure.position => best.clone(),
+            Some(best) if best.position == failure.position => Rc::new(Failure {
+                expected: either(&best.expected, expected),
+                ..failure.clone()
+            }),
+            _ => Rc::new(failure.clone()),
+        };
+        Failure {
+            furthest: Some(furthest),
+            ..failure
+        }
+    }
+
+    fn fail<T>(&self, expected: &str) -> ParseResult<'a, T> {
+        Err(self.failure(expected))
+    }
+}
+
+/// Why and where a parser failed, together with what it was parsing at the time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub position: usize,
+    pub expected: String,
+    pub context: Vec<String>,
+    /// Set by [`cut`], stops alternatives and repetitions from backtracking.
+    pub fatal: bool,
+    /// The failure that got furthest into the input on the way here, which may be this one.
+    pub furthest: Option<Rc<Failure>>,
+}
+
+pub type ParseResult<'a, T> = std::result::Result<(T, Input<'a>), Failure>;
+
+pub trait Parser<'a, T> {
+    fn parse(&self, input: Input<'a>) -> ParseResult<'a, T>;
+}
+
+impl<'a, T, F> Parser<'a, T> for F
+where
+    F: Fn(Input<'a>) -> ParseResult<'a, T>,
+{
+    fn parse(&self, input: Input<'a>) -> ParseResult<'a, T> {
+        self(input)
+    }
+}
+
+/// Runs a parser over the whole text, allowing only trailing whitespace to remain.
+///
+/// Backtracking combinators hide why a list or repetition stopped, so unless the failure is
+/// fatal the error reports the failure that got furthest into the input. Failures in text of
+/// more than one line are reported like those of `parse_lines`, with their line and column.
+pub fn parse_all<'a, T, P>(parser: P, text: &'a str) -> Result<T>
+where
+    P: Parser<'a, T>,
+{
+    let failure = match parser.parse(Input::new(text)) {
+        Ok((value, rest)) if rest.rest().trim().is_empty() => return Ok(value),
+        Ok((_, rest)) => rest.failure("end of input"),
+        Err(failure) => failure,
+    };
+    let failure = match failure.furthest.clone() {
+        _ if failure.fatal => failure,
+        Some(furthest) if furthest.position > failure.position => (*furthest).clone(),
+        Some(furthest) if furthest.position == failure.position => Failure {
+            expected: either(&furthest.expected, &failure.expected),
+            ..failure
+        },
+        _ => failure,
+    };
+    Err(to_error(text, failure))
+}
+
+/// Combines two descriptions of what was expected, listing every alternative once.
+fn either(first: &str, second: &str) -> String {
+    let mut alternatives: Vec<&str> = first.split(" or ").collect();
+    for alternative in second.split(" or ") {
+        if !alternatives.contains(&alternative) {
+            alternatives.push(alternative);
+        }
+    }
+    alternatives.join(" or ")
+}
+
+fn to_error(text: &str, failure: Failure) -> AOCError {
+    let mut reason = format!("expected {}", failure.expected);
+    for context in &failure.context {
+        reason.push_str(&format!(" in {}", context));
+    }
+    let rest = text[failure.position..].lines().next().unwrap_or_default();
+    let error = AOCError::parse(rest, &reason);
+    if text.trim_end().contains('\n') {
+        AOCError::ParseLines {
+            path: None,
+            errors: vec![located_error(text, failure.position, error)],
+        }
+    } else {
+        error
+    }
+}
+
+pub fn tag<'a>(literal: &'static str) -> impl Parser<'a, &'a str> {
+    move |input: Input<'a>| {
+        if input.rest().starts_with(literal) {
+            Ok((&input.rest()[..literal.len()], input.advance(literal.len())))
+        } else {
+            input.fail(&format!("'{}'", literal.escape_debug()))
+        }
+    }
+}
+
+/// A single character matching `predicate`.
+pub fn char_where<'a, F>(predicate: F, expected: &'static str) -> impl Parser<'a, char>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: Input<'a>| match input.rest().chars().next() {
+        Some(c) if predicate(c) => Ok((c, input.advance(c.len_utf8()))),
+        _ => input.fail(expected),
+    }
+}
+
+/// The longest non-empty run of characters matching `predicate`.
+pub fn take_while1<'a, F>(predicate: F, expected: &'static str) -> impl Parser<'a, &'a str>
+where
+    F: Fn(char) -> bool,
+{
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let length = rest
+            .char_indices()
+            .find(|&(_, c)| !predicate(c))
+            .map_or(rest.len(), |(index, _)| index);
+        if length == 0 {
+            input.fail(expected)
+        } else {
+            Ok((&rest[..length], input.advance(length)))
+        }
+    }
+}
+
+/// A decimal number with an optional sign.
+pub fn number<'a, T: FromStr>() -> impl Parser<'a, T> {
+    move |input: Input<'a>| {
+        let rest = input.rest();
+        let sign = usize::from(rest.starts_with(['-', '+']));
+        let digits = rest[sign..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        if digits == 0 {
+            return input.fail("a number");
+        }
+        match rest[..sign + digits].parse::<T>() {
+            Ok(value) => Ok((value, input.advance(sign + digits))),
+            Err(_) => input.fail("a number in range"),
+        }
+    }
+}
+
+/// Zero or more spaces or tabs.
+pub fn spaces<'a>() -> impl Parser<'a, ()> {
+    move |input: Input<'a>| {
+        let length = input.rest().len() - input.rest().trim_start_matches([' ', '\t']).len();
+        Ok(((), input.advance(length)))
+    }
+}
+
+pub fn line_ending<'a>() -> impl Parser<'a, &'a str> {
+    alt(tag("\n"), tag("\r\n"))
+}
+
+pub fn map<'a, T, U, P, F>(parser: P, f: F) -> impl Parser<'a, U>
+where
+    P: Parser<'a, T>,
+    F: Fn(T) -> U,
+{
+    move |input: Input<'a>| parser.parse(input).map(|(value, rest)| (f(value), rest))
+}
+
+/// Maps the parsed value with a fallible function, failing at the start of the value on error.
+/// The reason of a parse error, without a leading "expected", describes what was expected.
+pub fn map_res<'a, T, U, P, F>(parser: P, f: F) -> impl Parser<'a, U>
+where
+    P: Parser<'a, T>,
+    F: Fn(T) -> Result<U>,
+{
+    move |input: Input<'a>| {
+        let (value, rest) = parser.parse(input.clone())?;
+        match f(value) {
+            Ok(value) => Ok((value, rest)),
+            Err(AOCError::Parse { reason, .. }) => {
+                input.fail(reason.strip_prefix("expected ").unwrap_or(&reason))
+            }
+            Err(error) => input.fail(&error.to_string()),
+        }
+    }
+}
+
+/// Commits to `parser`: once it fails, enclosing alternatives and repetitions fail as well.
+pub fn cut<'a, T, P>(parser: P) -> impl Parser<'a, T>
+where
+    P: Parser<'a, T>,
+{
+    move |input: Input<'a>| {
+        parser.parse(input).map_err(|failure| Failure {
+            fatal: true,
+            ..failure
+        })
+    }
+}
+
+/// Names what is being parsed, so failures report it.
+pub fn context<'a, T, P>(name: &'static str, parser: P) -> impl Parser<'a, T>
+where
+    P: Parser<'a, T>,
+{
+    move |input: Input<'a>| {
+        parser.parse(input).map_err(|mut failure| {
+            failure.context.push(name.to_owned());
+            failure
+        })
+    }
+}
+
+pub fn pair<'a, A, B, PA, PB>(first: PA, second: PB) -> impl Parser<'a, (A, B)>
+where
+    PA: Parser<'a, A>,
+    PB: Parser<'a, B>,
+{
+    move |input: Input<'a>| {
+        let (a, rest) = first.parse(input)?;
+        let (b, rest) = second.parse(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+pub fn preceded<'a, A, T, PA, P>(prefix: PA, parser: P) -> impl Parser<'a, T>
+where
+    PA: Parser<'a, A>,
+    P: Parser<'a, T>,
+{
+    map(pair(prefix, parser), |(_, value)| value)
+}
+
+pub fn terminated<'a, T, B, P, PB>(parser: P, suffix: PB) -> impl Parser<'a, T>
+where
+    P: Parser<'a, T>,
+    PB: Parser<'a, B>,
+{
+    map(pair(parser, suffix), |(value, _)| value)
+}
+
+pub fn delimited<'a, A, T, B, PA, P, PB>(open: PA, parser: P, close: PB) -> impl Parser<'a, T>
+where
+    PA: Parser<'a, A>,
+    P: Parser<'a, T>,
+    PB: Parser<'a, B>,
+{
+    preceded(open, terminated(parser, close))
+}
+
+/// Tries `first`, then `second`, reporting the failure that got furthest.
+pub fn alt<'a, T, PA, PB>(first: PA, second: PB) -> impl Parser<'a, T>
+where
+    PA: Parser<'a, T>,
+    PB: Parser<'a, T>,
+{
+    move |input: Input<'a>| {
+        first.parse(input.clone()).or_else(|a| {
+            if a.fatal {
+                return Err(a);
+            }
+            second.parse(input.backtrack(&a)).map_err(|b| {
+                if b.fatal {
+                    b
+                } else if a.position == b.position {
+                    Failure {
+                        expected: either(&a.expected, &b.expected),
+                        ..b
+                    }
+                } else if a.position > b.position {
+                    a
+                } else {
+                    b
+                }
+            })
+        })
+    }
+}
+
+/// Zero or more repetitions of `parser`.
+pub fn many<'a, T, P>(parser: P) -> impl Parser<'a, Vec<T>>
+where
+    P: Parser<'a, T>,
+{
+    move |input: Input<'a>| repeat(&parser, Vec::new(), input)
+}
+
+/// One or more repetitions of `parser`.
+pub fn many1<'a, T, P>(parser: P) -> impl Parser<'a, Vec<T>>
+where
+    P: Parser<'a, T>,
+{
+    move |input: Input<'a>| {
+        let (first, rest) = parser.parse(input)?;
+        repeat(&parser, vec![first], rest)
+    }
+}
+
+fn repeat<'a, T, P>(parser: &P, mut values: Vec<T>, mut input: Input<'a>) -> ParseResult<'a, Vec<T>>
+where
+    P: Parser<'a, T>,
+{
+    loop {
+        match parser.parse(input.clone()) {
+            Ok((value, rest)) if rest.position() > input.position() => {
+                values.push(value);
+                input = rest;
+            }
+            Ok((_, rest)) => return Ok((values, rest)),
+            Err(failure) if failure.fatal => return Err(failure),
+            Err(failure) => return Ok((values, input.backtrack(&failure))),
+        }
+    }
+}
+
+/// One or more items separated by `separator`. A separator that is not followed by an item is
+/// left unconsumed.
+pub fn separated_list<'a, T, S, P, PS>(item: P, separator: PS) -> impl Parser<'a, Vec<T>>
+where
+    P: Parser<'a, T>,
+    PS: Parser<'a, S>,
+{
+    move |input: Input<'a>| {
+        let (first, mut input) = item.parse(input)?;
+        let mut values = vec![first];
+        loop {
+            match separator
+                .parse(input.clone())
+                .and_then(|(_, rest)| item.parse(rest))
+            {
+                Ok((value, rest)) => {
+                    values.push(value);
+                    input = rest;
+                }
+                Err(failure) if failure.fatal => return Err(failure),
+                Err(failure) => return Ok((values, input.backtrack(&failure))),
+            }
+        }
+    }
+}
+
+/// Lines of single digits, all of the same length.
+pub fn grid_of_digits<'a>() -> impl Parser<'a, Grid<u8>> {
+    let row = many1(map(char_where(|c| c.is_ascii_digit(), "a digit"), |c| {
+        c as u8 - b'0'
+    }));
+    move |input: Input<'a>| {
+        let (first, mut rest) = row.parse(input.clone())?;
+        let width = first.len();
+        let mut rows = vec![first];
+        loop {
+            let parsed = line_ending()
+                .parse(rest.clone())
+                .and_then(|(_, start)| row.parse(start.clone()).map(|row| (start, row)));
+            let (start, (cells, end)) = match parsed {
+                Ok(parsed) => parsed,
+                Err(failure) => {
+                    rest = rest.backtrack(&failure);
+                    break;
+                }
+            };
+            if cells.len() != width {
+                return start
+                    .advance(width.min(cells.len()))
+                    .fail(&format!("a row of {} digits", width))
+                    .map_err(|failure| Failure {
+                        fatal: true,
+                        ..failure
+                    });
+            }
+            rows.push(cells);
+            rest = end;
+        }
+        match Grid::new(rows) {
+            Ok(grid) => Ok((grid, rest)),
+            Err(_) => input.fail("rows of equal length"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn primitives_are_parsed() {
+        assert_eq!(parse_all(tag("up"), "up"), Ok("up"));
+        assert_eq!(parse_all(number::<i32>(), "-42\n"), Ok(-42));
+        assert_eq!(
+            parse_all(separated_list(number::<u8>(), tag(",")), "7,4,9"),
+            Ok(vec![7, 4, 9])
+        );
+        assert_eq!(
+            parse_all(delimited(tag("<"), many(number::<u8>()), tag(">")), "<5>"),
+            Ok(vec![5])
+        );
+        assert_eq!(
+            parse_all(many1(alt(tag("a"), tag("b"))), "abba"),
+            Ok(vec!["a", "b", "b", "a"])
+        );
+        assert_eq!(
+            parse_all(grid_of_digits(), "123\n456\n").map(|grid| grid.to_string()),
+            Ok("123\n456".to_owned())
+        );
+    }
+
+    #[test]
+    fn failures_are_located() {
+        let points = || {
+            context(
+                "a line",
+                separated_list(
+                    pair(terminated(number::<u8>(), tag(",")), number::<u8>()),
+                    tag(" -> "),
+                ),
+            )
+        };
+        assert_eq!(
+            parse_all(points(), "0,9 -> 5,x"),
+            Err(AOCError::parse("x", "expected a number"))
+        );
+        assert_eq!(
+            parse_all(points(), "0,9 => 5,9"),
+            Err(AOCError::parse(
+                " => 5,9",
+                "expected ' -> ' or end of input"
+            ))
+        );
+        assert_eq!(
+            parse_all(points(), "0;9"),
+            Err(AOCError::parse(";9", "expected ',' in a line"))
+        );
+        let located = |text| match parse_all(grid_of_digits(), text) {
+            Err(AOCError::ParseLines { mut errors, .. }) => {
+                let error = errors.remove(0);
+                (error.line_number, error.column, *error.error)
+            }
+            other => panic!("Unexpected result {:?}", other),
+        };
+        assert_eq!(
+            located("12\n3x\n"),
+            (
+                2,
+                Some(2),
+                AOCError::parse("x", "expected a row of 2 digits")
+            )
+        );
+        assert_eq!(
+            located("12\n345\n67\n"),
+            (
+                2,
+                Some(3),
+                AOCError::parse("5", "expected a row of 2 digits")
+            )
+        );
+        assert_eq!(
+            parse_all(alt(tag("up"), tag("down")), "left"),
+            Err(AOCError::parse("left", "expected 'up' or 'down'"))
+        );
+    }
+
+    #[test]
+    fn failures_carry_the_furthest_failure() {
+        let list = pair(separated_list(number::<u8>(), tag(",")), tag(";"));
+        let failure = list.parse(Input::new("1,x")).unwrap_err();
+        assert_eq!(failure.position, 1);
+        assert_eq!(
+            failure
+                .furthest
+                .map(|furthest| (furthest.position, furthest.expected.clone())),
+            Some((2, "a number".to_owned()))
+        );
+        assert_eq!(
+            parse_all(tag("a"), "b"),
+            Err(AOCError::parse("b", "expected 'a'"))
+        );
+    }
+
+    #[test]
+    fn mapped_failures_keep_the_reason() {
+        let digit = map_res(number::<u8>(), |n| {
+            if n < 10 {
+                Ok(n)
+            } else {
+                Err(AOCError::parse(&n.to_string(), "expected a single digit"))
+            }
+        });
+        assert_eq!(
+            parse_all(digit, "12"),
+            Err(AOCError::parse("12", "expected a single digit"))
+        );
+    }
+
+    #[test]
+    fn bingo_input_is_parsed() {
+        let row = preceded(spaces(), separated_list(number::<u8>(), spaces()));
+        let board = separated_list(row, line_ending());
+        let bingo = pair(
+            terminated(
+                separated_list(number::<u8>(), tag(",")),
+                pair(line_ending(), line_ending()),
+            ),
+            separated_list(board, pair(line_ending(), line_ending())),
+        );
+
+        let (numbers, boards) = parse_all(bingo, "7,4\n\n 1  2\n 3 4\n\n5 6\n7 8\n").unwrap();
+        assert_eq!(numbers, vec![7, 4]);
+        assert_eq!(
+            boards,
+            vec![vec![vec![1, 2], vec![3, 4]], vec![vec![5, 6], vec![7, 8]]]
+        );
+    }
+}
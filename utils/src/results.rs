@@ -1,47 +1,345 @@
 use std::error::Error;
 use std::fmt::Debug;
 use std::fmt::Display;
-
-// pub mod results {
+use std::path::Path;
+use std::path::PathBuf;
 
 pub type Result<T> = std::result::Result<T, AOCError>;
 
-#[derive(Debug, PartialEq)]
-pub struct AOCError(String);
+type Source = Box<dyn Error + Send + Sync + 'static>;
+
+#[derive(Debug)]
+pub enum AOCError {
+    Io(std::io::Error),
+    Parse {
+        input: String,
+        reason: String,
+        source: Option<Source>,
+    },
+    ParseLines {
+        path: Option<PathBuf>,
+        errors: Vec<LineError>,
+    },
+    Section {
+        number: usize,
+        error: Box<AOCError>,
+    },
+    NoSolution(String),
+    InvalidInput(String),
+    Other(String),
+}
+
+/// Exit code for failures without a more specific code, such as mismatching answers.
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code when a file could not be read or written or the site could not be reached; 2 is left
+/// to usage errors.
+pub const EXIT_IO: i32 = 3;
+/// Exit code when the input could not be parsed or is not valid for the puzzle.
+pub const EXIT_INVALID_INPUT: i32 = 4;
+/// Exit code when the input is valid, but has no solution.
+pub const EXIT_NO_SOLUTION: i32 = 5;
 
 impl AOCError {
     pub fn new_from_ref(message: &str) -> AOCError {
-        AOCError(message.to_owned())
+        AOCError::Other(message.to_owned())
     }
 
     pub fn new(message: String) -> AOCError {
-        AOCError(message)
+        AOCError::Other(message)
+    }
+
+    pub fn parse(input: &str, reason: &str) -> AOCError {
+        AOCError::Parse {
+            input: input.to_owned(),
+            reason: reason.to_owned(),
+            source: None,
+        }
+    }
+
+    pub fn parse_with_source<E>(input: &str, error: E) -> AOCError
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        AOCError::Parse {
+            input: input.to_owned(),
+            reason: error.to_string(),
+            source: Some(Box::new(error)),
+        }
+    }
+
+    /// Wraps the error of parsing `input`, keeping it as is when it already is an `AOCError`.
+    pub fn from_parse_error<E>(input: &str, error: E) -> AOCError
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        let boxed: Source = Box::new(error);
+        match boxed.downcast::<AOCError>() {
+            Ok(error) => *error,
+            Err(other) => AOCError::Parse {
+                input: input.to_owned(),
+                reason: other.to_string(),
+                source: Some(other),
+            },
+        }
+    }
+
+    pub fn no_solution(message: &str) -> AOCError {
+        AOCError::NoSolution(message.to_owned())
+    }
+
+    pub fn invalid_input(message: &str) -> AOCError {
+        AOCError::InvalidInput(message.to_owned())
+    }
+
+    /// Attaches the file the input was read from to line based parse errors.
+    pub fn with_path(self, path: &Path) -> AOCError {
+        match self {
+            AOCError::ParseLines { errors, .. } => AOCError::ParseLines {
+                path: Some(path.to_path_buf()),
+                errors,
+            },
+            AOCError::Section { number, error } => AOCError::Section {
+                number,
+                error: Box::new(error.with_path(path)),
+            },
+            other => other,
+        }
+    }
+
+    /// The process exit code for a run that failed with this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AOCError::Io(_) => EXIT_IO,
+            AOCError::Parse { .. } | AOCError::ParseLines { .. } | AOCError::InvalidInput(_) => {
+                EXIT_INVALID_INPUT
+            }
+            AOCError::Section { error, .. } => error.exit_code(),
+            AOCError::NoSolution(_) => EXIT_NO_SOLUTION,
+            AOCError::Other(_) => EXIT_FAILURE,
+        }
+    }
+
+    /// A short, stable name for the kind of error, for machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AOCError::Io(_) => "io",
+            AOCError::Parse { .. } | AOCError::ParseLines { .. } => "parse",
+            AOCError::Section { error, .. } => error.kind(),
+            AOCError::NoSolution(_) => "no_solution",
+            AOCError::InvalidInput(_) => "invalid_input",
+            AOCError::Other(_) => "other",
+        }
+    }
+}
+
+/// A failure to parse a single line, pointing at the offending text where possible.
+#[derive(Debug, PartialEq)]
+pub struct LineError {
+    pub line_number: usize,
+    pub line: String,
+    pub column: Option<usize>,
+    pub error: Box<AOCError>,
+}
+
+impl LineError {
+    pub fn new(line_number: usize, line: &str, error: AOCError) -> LineError {
+        let column = match &error {
+            AOCError::Parse { input, .. } if !input.is_empty() => line
+                .find(input.as_str())
+                .map(|index| line[..index].chars().count() + 1),
+            _ => None,
+        };
+        LineError {
+            line_number,
+            line: line.to_owned(),
+            column,
+            error: Box::new(error),
+        }
+    }
+
+    pub fn from_error<E>(line_number: usize, line: &str, error: E) -> LineError
+    where
+        E: Error + Send + Sync + 'static,
+    {
+        LineError::new(line_number, line, AOCError::from_parse_error(line, error))
+    }
+
+    fn caret_width(&self) -> usize {
+        match self.error.as_ref() {
+            AOCError::Parse { input, .. } => input.chars().count().max(1),
+            _ => 1,
+        }
+    }
+
+    pub fn diagnostic(&self, path: Option<&Path>) -> String {
+        let gutter = " ".repeat(self.line_number.to_string().len());
+        let location = format!(
+            "{}:{}{}",
+            path.map_or_else(|| "<input>".to_owned(), |p| p.display().to_string()),
+            self.line_number,
+            self.column.map_or_else(String::new, |c| format!(":{}", c))
+        );
+        let mut diagnostic = format!(
+            "error: {}\n{}--> {}\n{} |\n{} | {}",
+            self.error, gutter, location, gutter, self.line_number, self.line
+        );
+        if let Some(column) = self.column {
+            diagnostic.push_str(&format!(
+                "\n{} | {}{}",
+                gutter,
+                " ".repeat(column - 1),
+                "^".repeat(self.caret_width())
+            ));
+        }
+        diagnostic
     }
 }
 
 impl From<&'static str> for AOCError {
     fn from(message: &'static str) -> AOCError {
-        AOCError::new(message.to_owned())
+        AOCError::new_from_ref(message)
     }
 }
 
 impl From<std::io::Error> for AOCError {
     fn from(error: std::io::Error) -> AOCError {
-        AOCError::new(error.to_string())
+        AOCError::Io(error)
     }
 }
 
 impl From<std::num::ParseIntError> for AOCError {
     fn from(error: std::num::ParseIntError) -> AOCError {
-        AOCError::new(error.to_string())
+        AOCError::parse_with_source("", error)
     }
 }
 
 impl Display for AOCError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Error: {}", self.0)
+        match self {
+            AOCError::Io(error) => write!(f, "I/O error: {}", error),
+            AOCError::Parse { input, reason, .. } if input.is_empty() => {
+                write!(f, "Parse error: {}", reason)
+            }
+            AOCError::Parse { input, reason, .. } => {
+                write!(f, "Failed to parse '{}': {}", input, reason)
+            }
+            AOCError::ParseLines { path, errors } => {
+                write!(f, "{} line(s) failed to parse", errors.len())?;
+                for error in errors {
+                    write!(f, "\n{}", error.diagnostic(path.as_deref()))?;
+                }
+                Ok(())
+            }
+            AOCError::Section { number, error } => write!(f, "Section {}: {}", number, error),
+            AOCError::NoSolution(message) => write!(f, "No solution: {}", message),
+            AOCError::InvalidInput(message) => write!(f, "Invalid input: {}", message),
+            AOCError::Other(message) => write!(f, "Error: {}", message),
+        }
+    }
+}
+
+impl Error for AOCError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AOCError::Io(error) => Some(error),
+            AOCError::Parse {
+                source: Some(source),
+                ..
+            } => Some(source.as_ref()),
+            AOCError::ParseLines { errors, .. } => errors
+                .first()
+                .map(|error| error.error.as_ref() as &(dyn Error + 'static)),
+            AOCError::Section { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
     }
 }
 
-impl Error for AOCError {}
-// }
+impl PartialEq for AOCError {
+    fn eq(&self, other: &AOCError) -> bool {
+        match (self, other) {
+            (AOCError::Io(a), AOCError::Io(b)) => {
+                a.kind() == b.kind() && a.to_string() == b.to_string()
+            }
+            (
+                AOCError::Parse {
+                    input: a_input,
+                    reason: a_reason,
+                    ..
+                },
+                AOCError::Parse {
+                    input: b_input,
+                    reason: b_reason,
+                    ..
+                },
+            ) => a_input == b_input && a_reason == b_reason,
+            (
+                AOCError::ParseLines {
+                    path: a_path,
+                    errors: a_errors,
+                },
+                AOCError::ParseLines {
+                    path: b_path,
+                    errors: b_errors,
+                },
+            ) => a_path == b_path && a_errors == b_errors,
+            (
+                AOCError::Section {
+                    number: a_number,
+                    error: a_error,
+                },
+                AOCError::Section {
+                    number: b_number,
+                    error: b_error,
+                },
+            ) => a_number == b_number && a_error == b_error,
+            (AOCError::NoSolution(a), AOCError::NoSolution(b)) => a == b,
+            (AOCError::InvalidInput(a), AOCError::InvalidInput(b)) => a == b,
+            (AOCError::Other(a), AOCError::Other(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn source_is_preserved() {
+        let error: AOCError = "x12".parse::<u8>().unwrap_err().into();
+        assert!(matches!(error, AOCError::Parse { .. }));
+        assert!(error
+            .source()
+            .unwrap()
+            .downcast_ref::<std::num::ParseIntError>()
+            .is_some());
+
+        let error: AOCError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+        assert!(matches!(&error, AOCError::Io(io) if io.kind() == std::io::ErrorKind::NotFound));
+        assert!(error.source().is_some());
+
+        assert!(AOCError::no_solution("No winner found").source().is_none());
+    }
+
+    #[test]
+    fn exit_codes_follow_the_error_kind() {
+        let io: AOCError = std::io::Error::from(std::io::ErrorKind::NotFound).into();
+        assert_eq!(io.exit_code(), EXIT_IO);
+        assert_eq!(
+            AOCError::parse("x", "not a number").exit_code(),
+            EXIT_INVALID_INPUT
+        );
+        assert_eq!(
+            AOCError::Section {
+                number: 2,
+                error: Box::new(AOCError::invalid_input("ragged board")),
+            }
+            .exit_code(),
+            EXIT_INVALID_INPUT
+        );
+        assert_eq!(AOCError::no_solution("none").exit_code(), EXIT_NO_SOLUTION);
+        assert_eq!(AOCError::new_from_ref("oops").exit_code(), EXIT_FAILURE);
+    }
+}
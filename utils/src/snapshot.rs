@@ -0,0 +1,168 @@
+use crate::results::Result;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+/// Environment variable that makes snapshot assertions rewrite the stored files.
+pub const UPDATE_SNAPSHOTS: &str = "UPDATE_SNAPSHOTS";
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Ansi {
+    Keep,
+    Strip,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Matches,
+    Updated,
+    Differs { expected: Option<String> },
+}
+
+/// Compares `actual` with `snapshots/<name>.snap` below `dir`, rewriting it in update mode.
+///
+/// Panics with both versions when they differ, so it can be used directly from a test;
+/// see [`assert_snapshot!`](crate::assert_snapshot) for the macro that fills in `dir`.
+pub fn assert_snapshot(dir: &str, name: &str, actual: &str, ansi: Ansi) {
+    let path = snapshot_path(dir, name);
+    let update = env::var_os(UPDATE_SNAPSHOTS).is_some_and(|value| value != "0");
+    match compare_snapshot(&path, actual, ansi, update) {
+        Ok(Comparison::Matches) | Ok(Comparison::Updated) => {}
+        Ok(Comparison::Differs { expected: None }) => panic!(
+            "Snapshot {} does not exist, rerun with {}=1 to create it:\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS,
+            prepare(actual, ansi)
+        ),
+        Ok(Comparison::Differs {
+            expected: Some(expected),
+        }) => panic!(
+            "Snapshot {} differs, rerun with {}=1 to accept the change\n--- stored\n{}\n+++ actual\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS,
+            expected,
+            prepare(actual, ansi)
+        ),
+        Err(error) => panic!("Snapshot {}: {}", path.display(), error),
+    }
+}
+
+pub fn snapshot_path(dir: &str, name: &str) -> PathBuf {
+    Path::new(dir)
+        .join("snapshots")
+        .join(format!("{}.snap", name))
+}
+
+pub fn compare_snapshot(path: &Path, actual: &str, ansi: Ansi, update: bool) -> Result<Comparison> {
+    let actual = prepare(actual, ansi);
+    let expected = if path.exists() {
+        Some(fs::read_to_string(path)?)
+    } else {
+        None
+    };
+
+    if expected.as_deref() == Some(actual.as_str()) {
+        Ok(Comparison::Matches)
+    } else if update {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, actual)?;
+        Ok(Comparison::Updated)
+    } else {
+        Ok(Comparison::Differs { expected })
+    }
+}
+
+/// The text as it is stored: optionally without escape codes, and ending in a newline.
+fn prepare(text: &str, ansi: Ansi) -> String {
+    let mut text = match ansi {
+        Ansi::Keep => text.to_owned(),
+        Ansi::Strip => strip_ansi(text),
+    };
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    text
+}
+
+/// Removes ANSI escape sequences such as colours and text styles.
+pub fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' && chars.peek() == Some(&'[') {
+            chars.next();
+            for c in chars.by_ref() {
+                if ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+/// Checks the rendered value against `snapshots/<name>.snap` of the calling crate.
+///
+/// ```ignore
+/// assert_snapshot!("board", board);
+/// assert_snapshot!("basins", colorize(&map, &basins), Ansi::Strip);
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $actual:expr) => {
+        $crate::assert_snapshot!($name, $actual, $crate::snapshot::Ansi::Keep)
+    };
+    ($name:expr, $actual:expr, $ansi:expr) => {
+        $crate::snapshot::assert_snapshot(
+            env!("CARGO_MANIFEST_DIR"),
+            $name,
+            &$actual.to_string(),
+            $ansi,
+        )
+    };
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn ansi_codes_are_stripped() {
+        assert_eq!(strip_ansi("\u{1b}[1;32m42\u{1b}[0m done"), "42 done");
+        assert_eq!(strip_ansi("no codes [here]"), "no codes [here]");
+    }
+
+    #[test]
+    fn snapshots_are_compared_and_updated() {
+        let dir = env::temp_dir().join(format!("aoc-snapshot-{}", std::process::id()));
+        let path = dir.join("snapshots").join("grid.snap");
+
+        assert_eq!(
+            compare_snapshot(&path, "12\n34", Ansi::Keep, false).unwrap(),
+            Comparison::Differs { expected: None }
+        );
+        assert_eq!(
+            compare_snapshot(&path, "\u{1b}[31m12\u{1b}[0m\n34", Ansi::Strip, true).unwrap(),
+            Comparison::Updated
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "12\n34\n");
+        assert_eq!(
+            compare_snapshot(&path, "12\n34\n", Ansi::Keep, false).unwrap(),
+            Comparison::Matches
+        );
+        assert_eq!(
+            compare_snapshot(&path, "12\n43", Ansi::Keep, false).unwrap(),
+            Comparison::Differs {
+                expected: Some("12\n34\n".to_owned())
+            }
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
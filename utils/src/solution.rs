@@ -0,0 +1,320 @@
+use crate::bench::BenchSettings;
+use crate::bench::Samples;
+use crate::bench::Timings;
+use crate::results::AOCError;
+use crate::results::Result;
+use std::fmt::Display;
+use std::hint::black_box;
+use std::io::BufRead;
+use std::str::FromStr;
+use std::time::Duration;
+use std::time::Instant;
+use tracing::field;
+use tracing::info_span;
+
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part_one(input: &Self::Input) -> Result<Self::PartOne>;
+
+    fn part_two(input: &Self::Input) -> Result<Self::PartTwo>;
+
+    /// Whether the parts are solved straight from a reader, without holding the whole input.
+    const STREAMING: bool = false;
+
+    /// Solves part one from a reader; streaming days override this to run in constant memory.
+    fn part_one_from_reader(reader: &mut dyn BufRead) -> Result<Self::PartOne> {
+        Self::part_one(&Self::parse(&read_all(reader)?)?)
+    }
+
+    fn part_two_from_reader(reader: &mut dyn BufRead) -> Result<Self::PartTwo> {
+        Self::part_two(&Self::parse(&read_all(reader)?)?)
+    }
+}
+
+fn read_all(reader: &mut dyn BufRead) -> Result<String> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Opens the input, once for every part that is solved from a reader.
+pub type Open<'a> = dyn FnMut() -> Result<Box<dyn BufRead + 'a>> + 'a;
+
+/// Object safe view on a [`Solution`] so days can be stored and run generically.
+pub trait Puzzle {
+    fn day(&self) -> u8;
+
+    fn solve(&self, input: &str, part: Part) -> Result<Outcome>;
+
+    /// Whether [`Puzzle::solve_reader`] streams the input instead of reading it as a whole.
+    fn streams(&self) -> bool;
+
+    /// Solves the input returned by `open`, which is opened again for each part of a
+    /// streaming day and read once into memory otherwise.
+    ///
+    /// A streaming day does not parse separately, so its reading time counts towards the parts.
+    fn solve_reader(&self, open: &mut Open, part: Part) -> Result<Outcome>;
+
+    /// Times parsing and both parts separately, discarding the warm-up iterations.
+    fn bench(&self, input: &str, settings: BenchSettings) -> Result<Timings>;
+}
+
+impl<S: Solution> Puzzle for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn solve(&self, input: &str, part: Part) -> Result<Outcome> {
+        let start = Instant::now();
+        let parsed = info_span!("parse", bytes = input.len()).in_scope(|| S::parse(input))?;
+        let mut elapsed = Elapsed {
+            parse: start.elapsed(),
+            ..Elapsed::default()
+        };
+
+        let part_one = if part.includes_one() {
+            let start = Instant::now();
+            let result = solve_part(1, || S::part_one(&parsed));
+            elapsed.part_one = Some(start.elapsed());
+            Some(result)
+        } else {
+            None
+        };
+        let part_two = if part.includes_two() {
+            let start = Instant::now();
+            let result = solve_part(2, || S::part_two(&parsed));
+            elapsed.part_two = Some(start.elapsed());
+            Some(result)
+        } else {
+            None
+        };
+        Ok(Outcome {
+            part_one,
+            part_two,
+            elapsed,
+        })
+    }
+
+    fn streams(&self) -> bool {
+        S::STREAMING
+    }
+
+    fn solve_reader(&self, open: &mut Open, part: Part) -> Result<Outcome> {
+        if !S::STREAMING {
+            let start = Instant::now();
+            let input = read_all(&mut open()?)?;
+            let read = start.elapsed();
+            let mut outcome = self.solve(&input, part)?;
+            outcome.elapsed.read = read;
+            return Ok(outcome);
+        }
+
+        let mut elapsed = Elapsed::default();
+        let part_one = if part.includes_one() {
+            let start = Instant::now();
+            let mut reader = open()?;
+            let result = solve_part(1, || S::part_one_from_reader(&mut reader));
+            elapsed.part_one = Some(start.elapsed());
+            Some(result)
+        } else {
+            None
+        };
+        let part_two = if part.includes_two() {
+            let start = Instant::now();
+            let mut reader = open()?;
+            let result = solve_part(2, || S::part_two_from_reader(&mut reader));
+            elapsed.part_two = Some(start.elapsed());
+            Some(result)
+        } else {
+            None
+        };
+        Ok(Outcome {
+            part_one,
+            part_two,
+            elapsed,
+        })
+    }
+
+    fn bench(&self, input: &str, settings: BenchSettings) -> Result<Timings> {
+        let mut samples = Samples::default();
+        for iteration in 0..settings.warmup + settings.iterations {
+            let start = Instant::now();
+            let parsed = black_box(S::parse(input)?);
+            let parse = start.elapsed();
+
+            let start = Instant::now();
+            black_box(S::part_one(&parsed)?);
+            let part_one = start.elapsed();
+
+            let start = Instant::now();
+            black_box(S::part_two(&parsed)?);
+            let part_two = start.elapsed();
+
+            if iteration >= settings.warmup {
+                samples.push(parse, part_one, part_two);
+            }
+        }
+        samples.timings()
+    }
+}
+
+/// Runs a part in its own span, which records the answer once it is known.
+fn solve_part<A: Display>(part: u8, solve: impl FnOnce() -> Result<A>) -> Result<String> {
+    let span = info_span!("part", part, answer = field::Empty);
+    let _entered = span.enter();
+    let result = solve().map(|answer| answer.to_string());
+    if let Ok(answer) = &result {
+        span.record("answer", field::display(answer));
+    }
+    result
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Outcome {
+    pub part_one: Option<Result<String>>,
+    pub part_two: Option<Result<String>>,
+    pub elapsed: Elapsed,
+}
+
+impl Outcome {
+    /// The error of the first part that failed, if any.
+    pub fn first_error(&self) -> Option<&AOCError> {
+        [&self.part_one, &self.part_two]
+            .into_iter()
+            .find_map(|result| result.as_ref()?.as_ref().err())
+    }
+}
+
+/// Wall-clock time spent in every phase of a run, parts that were not run are `None`.
+#[derive(Debug, PartialEq, Eq, Default, Copy, Clone)]
+pub struct Elapsed {
+    pub read: Duration,
+    pub parse: Duration,
+    pub part_one: Option<Duration>,
+    pub part_two: Option<Duration>,
+}
+
+impl Elapsed {
+    pub fn total(&self) -> Duration {
+        self.read
+            + self.parse
+            + self.part_one.unwrap_or_default()
+            + self.part_two.unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn includes_one(&self) -> bool {
+        matches!(self, Part::One | Part::Both)
+    }
+
+    pub fn includes_two(&self) -> bool {
+        matches!(self, Part::Two | Part::Both)
+    }
+
+    pub fn level(&self) -> Option<u8> {
+        match self {
+            Part::One => Some(1),
+            Part::Two => Some(2),
+            Part::Both => None,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        match input {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            "both" => Ok(Part::Both),
+            unrec => Err(AOCError::parse(unrec, "expected 1, 2 or both")),
+        }
+    }
+}
+
+/// Generates a test per named input in the day's `input/` directory, checking both parts.
+///
+/// Every case reads `input/<name>.input`, parses it with the solution and compares both
+/// answers, also from a reader for streaming days. Attributes such as `#[ignore]` are passed
+/// on to the generated test.
+///
+/// ```ignore
+/// aoc_tests!(Day03, test => (198, 230), puzzle => (1540244, 4203981));
+/// ```
+#[macro_export]
+macro_rules! aoc_tests {
+    ($solution:ty, $($(#[$meta:meta])* $name:ident => ($one:expr, $two:expr)),+ $(,)?) => {
+        #[cfg(test)]
+        mod input_tests {
+
+            use super::*;
+
+            $(
+                #[test]
+                $(#[$meta])*
+                fn $name() {
+                    let path = concat!(
+                        env!("CARGO_MANIFEST_DIR"),
+                        "/input/",
+                        stringify!($name),
+                        ".input"
+                    );
+                    let text = ::std::fs::read_to_string(path)
+                        .unwrap_or_else(|err| panic!("Failed to read {}: {}", path, err));
+                    let input = <$solution as $crate::solution::Solution>::parse(&text)
+                        .unwrap_or_else(|err| panic!("Failed to parse {}: {}", path, err));
+
+                    assert_eq!(
+                        <$solution as $crate::solution::Solution>::part_one(&input),
+                        Ok($one),
+                        "part one of {}",
+                        path
+                    );
+                    assert_eq!(
+                        <$solution as $crate::solution::Solution>::part_two(&input),
+                        Ok($two),
+                        "part two of {}",
+                        path
+                    );
+
+                    if <$solution as $crate::solution::Solution>::STREAMING {
+                        let mut reader = text.as_bytes();
+                        assert_eq!(
+                            <$solution as $crate::solution::Solution>::part_one_from_reader(
+                                &mut reader
+                            ),
+                            Ok($one),
+                            "streamed part one of {}",
+                            path
+                        );
+                        let mut reader = text.as_bytes();
+                        assert_eq!(
+                            <$solution as $crate::solution::Solution>::part_two_from_reader(
+                                &mut reader
+                            ),
+                            Ok($two),
+                            "streamed part two of {}",
+                            path
+                        );
+                    }
+                }
+            )+
+        }
+    };
+}
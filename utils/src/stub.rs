@@ -0,0 +1,61 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::thread::JoinHandle;
+
+pub struct StubServer {
+    pub url: String,
+    handle: JoinHandle<Vec<String>>,
+}
+
+impl StubServer {
+    /// Serves the given responses, one per connection, and records the raw requests.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> StubServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let request = read_request(&mut reader);
+                    write!(
+                        reader.get_mut(),
+                        "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                    request
+                })
+                .collect()
+        });
+        StubServer { url, handle }
+    }
+
+    pub fn requests(self) -> Vec<String> {
+        self.handle.join().unwrap()
+    }
+}
+
+fn read_request<R: BufRead>(reader: &mut R) -> String {
+    let mut request = String::new();
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if let Some(length) = line.to_lowercase().strip_prefix("content-length:") {
+            content_length = length.trim().parse().unwrap();
+        }
+        request.push_str(&line);
+        if line == "\r\n" || line.is_empty() {
+            break;
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    request.push_str(&String::from_utf8_lossy(&body));
+    request
+}
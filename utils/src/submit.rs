@@ -0,0 +1,397 @@
+use crate::client::Client;
+use crate::results::AOCError;
+use crate::results::Result;
+use itertools::Itertools;
+use std::fmt::Display;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+use tracing::info;
+use tracing::warn;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    Wait,
+    WrongLevel,
+    /// The page was not recognised, e.g. a login redirect; such submissions are not recorded.
+    Unknown,
+}
+
+impl Verdict {
+    pub fn is_wrong(&self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Incorrect
+        )
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        let output = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::Incorrect => "incorrect",
+            Verdict::Wait => "wait",
+            Verdict::WrongLevel => "wrong-level",
+            Verdict::Unknown => "unknown",
+        };
+        write!(f, "{}", output)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        match input {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "incorrect" => Ok(Verdict::Incorrect),
+            "wait" => Ok(Verdict::Wait),
+            "wrong-level" => Ok(Verdict::WrongLevel),
+            unrec => Err(AOCError::parse(unrec, "not a verdict")),
+        }
+    }
+}
+
+/// Interprets the response page of the answer endpoint, returning the verdict and the cooldown in seconds.
+pub fn parse_response(page: &str) -> (Verdict, u64) {
+    let verdict = if page.contains("That's the right answer") {
+        Verdict::Correct
+    } else if page.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if page.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if page.contains("That's not the right answer") {
+        Verdict::Incorrect
+    } else if page.contains("You gave an answer too recently") {
+        Verdict::Wait
+    } else if page.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown
+    };
+    (verdict, parse_cooldown(page))
+}
+
+fn parse_cooldown(page: &str) -> u64 {
+    if let Some(end) = page.find(" left to wait") {
+        let start = page[..end].rfind("have ").map(|i| i + 5).unwrap_or(end);
+        page[start..end]
+            .split_whitespace()
+            .map(|amount| {
+                let unit_start = amount.char_indices().last().map_or(0, |(index, _)| index);
+                let (value, unit) = amount.split_at(unit_start);
+                let value = value.parse::<u64>().unwrap_or(0);
+                match unit {
+                    "h" => value * 3600,
+                    "m" => value * 60,
+                    _ => value,
+                }
+            })
+            .sum()
+    } else if page.contains("wait one minute") {
+        60
+    } else if let Some(start) = page.find("wait ").map(|i| i + 5) {
+        page[start..]
+            .split_whitespace()
+            .next_tuple()
+            .filter(|(_, unit)| unit.starts_with("minutes"))
+            .and_then(|(minutes, _)| minutes.parse::<u64>().ok())
+            .map(|minutes| minutes * 60)
+            .unwrap_or(0)
+    } else {
+        0
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Submission {
+    pub timestamp: u64,
+    pub day: u8,
+    pub level: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub cooldown: u64,
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            f,
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp, self.day, self.level, self.answer, self.verdict, self.cooldown
+        )
+    }
+}
+
+impl FromStr for Submission {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        let (timestamp, day, level, answer, verdict, cooldown) = input
+            .split('\t')
+            .collect_tuple()
+            .ok_or_else(|| AOCError::parse(input, "malformed submission"))?;
+        Ok(Submission {
+            timestamp: timestamp.parse()?,
+            day: day.parse()?,
+            level: level.parse()?,
+            answer: answer.to_owned(),
+            verdict: verdict.parse()?,
+            cooldown: cooldown.parse()?,
+        })
+    }
+}
+
+/// Every answer that was ever submitted, backed by a tab separated file.
+#[derive(Debug)]
+pub struct SubmissionLog {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    pub fn load(path: &Path) -> Result<SubmissionLog> {
+        let submissions = if path.exists() {
+            fs::read_to_string(path)?
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(|line| line.parse::<Submission>())
+                .try_collect()?
+        } else {
+            Vec::new()
+        };
+        Ok(SubmissionLog {
+            path: path.to_path_buf(),
+            submissions,
+        })
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    pub fn record(&mut self, submission: Submission) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", submission)?;
+        self.submissions.push(submission);
+        Ok(())
+    }
+
+    /// Refuses answers that are known to be wrong and submissions during a cooldown.
+    pub fn check(&self, day: u8, level: u8, answer: &str, now: u64) -> Result<()> {
+        if let Some(wait_until) = self
+            .submissions
+            .iter()
+            .map(|s| s.timestamp + s.cooldown)
+            .max()
+            .filter(|&until| until > now)
+        {
+            return Err(AOCError::new(format!(
+                "Answers are throttled, wait another {}s",
+                wait_until - now
+            )));
+        }
+
+        let previous = self
+            .submissions
+            .iter()
+            .filter(|s| s.day == day && s.level == level);
+        let numeric = answer.parse::<i64>().ok();
+        for submission in previous {
+            let previous_numeric = submission.answer.parse::<i64>().ok();
+            let refused = match submission.verdict {
+                Verdict::Correct => true,
+                verdict if verdict.is_wrong() && submission.answer == answer => true,
+                Verdict::TooHigh => numeric
+                    .zip(previous_numeric)
+                    .is_some_and(|(new, old)| new >= old),
+                Verdict::TooLow => numeric
+                    .zip(previous_numeric)
+                    .is_some_and(|(new, old)| new <= old),
+                _ => false,
+            };
+            if refused {
+                return Err(AOCError::new(format!(
+                    "Refusing to submit {} for day {} part {}: {} was {}",
+                    answer, day, level, submission.answer, submission.verdict
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Client {
+    pub fn submit_answer(&self, day: u8, level: u8, answer: &str) -> Result<(Verdict, u64)> {
+        let url = self.day_url(day, "answer");
+        info!(
+            "Submitting {} for day {} part {} to {}",
+            answer, day, level, url
+        );
+        let page = self.post_form(&url, &[("level", &level.to_string()), ("answer", answer)])?;
+        Ok(parse_response(&page))
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+pub fn submit(
+    client: &Client,
+    log: &mut SubmissionLog,
+    day: u8,
+    level: u8,
+    answer: &str,
+) -> Result<Verdict> {
+    let timestamp = now();
+    log.check(day, level, answer, timestamp)?;
+    let (verdict, cooldown) = client.submit_answer(day, level, answer)?;
+    if verdict == Verdict::Unknown {
+        warn!("Unrecognised response to the submission, it is not recorded");
+        return Ok(verdict);
+    }
+    log.record(Submission {
+        timestamp,
+        day,
+        level,
+        answer: answer.to_owned(),
+        verdict,
+        cooldown,
+    })?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::stub::StubServer;
+    use std::env;
+
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        Please wait one minute before trying again.</p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait \
+        after submitting an answer before trying again.  You have 1m 31s left to wait.</p></article>";
+    const CORRECT: &str = "<article><p>That's the right answer!  You are one gold star closer \
+        to finding the sleigh keys.</p></article>";
+
+    fn temp_log(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("aoc-submit-{}-{}.tsv", name, std::process::id()))
+    }
+
+    #[test]
+    fn responses_are_interpreted() {
+        assert_eq!(parse_response(TOO_HIGH), (Verdict::TooHigh, 60));
+        assert_eq!(parse_response(TOO_RECENT), (Verdict::Wait, 91));
+        assert_eq!(parse_response(CORRECT), (Verdict::Correct, 0));
+        assert_eq!(
+            parse_response("your answer is too low.  Please wait 5 minutes before trying again."),
+            (Verdict::TooLow, 300)
+        );
+        assert_eq!(
+            parse_response(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            (Verdict::WrongLevel, 0)
+        );
+        assert_eq!(
+            parse_response("<html>Please log in</html>"),
+            (Verdict::Unknown, 0)
+        );
+        assert_eq!(
+            parse_response("You have 2m 5é left to wait."),
+            (Verdict::Unknown, 125)
+        );
+    }
+
+    #[test]
+    fn unknown_responses_are_not_recorded() {
+        let path = temp_log("unknown");
+        let server = StubServer::serve(vec![(200, "<html>Please log in</html>")]);
+        let client = Client::new("abc123".to_owned()).with_base_url(&server.url);
+        let mut log = SubmissionLog::load(&path).unwrap();
+
+        assert_eq!(submit(&client, &mut log, 1, 1, "7"), Ok(Verdict::Unknown));
+        server.requests();
+        assert_eq!(log.submissions(), &[]);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn submissions_are_recorded_and_throttled() {
+        let path = temp_log("throttle");
+        let server = StubServer::serve(vec![(200, TOO_HIGH)]);
+        let client = Client::new("abc123".to_owned()).with_base_url(&server.url);
+        let mut log = SubmissionLog::load(&path).unwrap();
+
+        assert_eq!(
+            submit(&client, &mut log, 1, 1, "1000"),
+            Ok(Verdict::TooHigh)
+        );
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /2021/day/1/answer "));
+        assert!(requests[0].ends_with("level=1&answer=1000"));
+
+        assert!(submit(&client, &mut log, 1, 2, "5").is_err());
+
+        let reloaded = SubmissionLog::load(&path).unwrap();
+        assert_eq!(reloaded.submissions(), log.submissions());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn known_wrong_answers_are_refused() {
+        let log = SubmissionLog {
+            path: temp_log("refused"),
+            submissions: vec![
+                Submission {
+                    timestamp: 100,
+                    day: 7,
+                    level: 2,
+                    answer: "500".to_owned(),
+                    verdict: Verdict::TooHigh,
+                    cooldown: 60,
+                },
+                Submission {
+                    timestamp: 200,
+                    day: 7,
+                    level: 2,
+                    answer: "100".to_owned(),
+                    verdict: Verdict::TooLow,
+                    cooldown: 60,
+                },
+            ],
+        };
+
+        assert!(log.check(7, 2, "500", 1000).is_err());
+        assert!(log.check(7, 2, "600", 1000).is_err());
+        assert!(log.check(7, 2, "50", 1000).is_err());
+        assert!(log.check(7, 2, "250", 250).is_err());
+        assert_eq!(log.check(7, 2, "250", 1000), Ok(()));
+        assert_eq!(log.check(7, 1, "500", 1000), Ok(()));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::output::color_enabled;
+use crate::output::Stream;
+use crate::results::AOCError;
+use crate::results::Result;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::str::FromStr;
+use tracing::level_filters::LevelFilter;
+use tracing::Level;
+use tracing::Metadata;
+use tracing_chrome::ChromeLayerBuilder;
+use tracing_chrome::FlushGuard;
+use tracing_subscriber::filter::filter_fn;
+use tracing_subscriber::fmt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::Layer;
+use tracing_subscriber::Registry;
+use tracing_tree::time::Uptime;
+use tracing_tree::HierarchicalLayer;
+
+/// How log events are written to stderr.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum LogStyle {
+    /// One line per event, prefixed with the spans it happened in.
+    Flat,
+    /// Spans as an indented tree, with the time since the span was entered.
+    Tree,
+}
+
+impl FromStr for LogStyle {
+    type Err = AOCError;
+
+    fn from_str(input: &str) -> std::result::Result<Self, <Self as std::str::FromStr>::Err> {
+        match input {
+            "flat" => Ok(LogStyle::Flat),
+            "tree" => Ok(LogStyle::Tree),
+            unrec => Err(AOCError::parse(unrec, "expected flat or tree")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TraceSettings {
+    /// Overrides `RUST_LOG` for stderr.
+    pub level: Option<LevelFilter>,
+    pub style: LogStyle,
+    /// File to write every span to, in the Chrome trace event format.
+    pub chrome: Option<PathBuf>,
+}
+
+/// Keeps the Chrome trace writer alive; the file is completed when this is dropped.
+#[must_use]
+pub struct TraceGuard {
+    _chrome: Option<FlushGuard>,
+}
+
+type BoxedLayer = Box<dyn Layer<Registry> + Send + Sync>;
+
+/// Installs the global subscriber: logging to stderr, and a Chrome trace when a file is given.
+///
+/// The Chrome trace records spans of every level regardless of the log level, so the time
+/// spent in each solver step shows up without having to enable debug logging.
+pub fn init_tracing(settings: &TraceSettings) -> Result<TraceGuard> {
+    let filter = match settings.level {
+        Some(level) => EnvFilter::default().add_directive(level.into()),
+        None => EnvFilter::builder()
+            .with_default_directive(LevelFilter::INFO.into())
+            .from_env_lossy(),
+    };
+    let console: BoxedLayer = match settings.style {
+        LogStyle::Flat => fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_ansi(color_enabled(Stream::Stderr))
+            .without_time()
+            .with_filter(filter)
+            .boxed(),
+        LogStyle::Tree => HierarchicalLayer::new(2)
+            .with_writer(std::io::stderr)
+            .with_ansi(color_enabled(Stream::Stderr))
+            .with_targets(true)
+            .with_bracketed_fields(true)
+            .with_timer(Uptime::default())
+            .with_filter(filter)
+            .boxed(),
+    };
+    let mut layers = vec![console];
+
+    let chrome = match &settings.chrome {
+        Some(path) => {
+            let file = File::create(path).map_err(|err| {
+                AOCError::Io(std::io::Error::new(
+                    err.kind(),
+                    format!("Failed to create trace file '{}': {}", path.display(), err),
+                ))
+            })?;
+            let (layer, guard) = ChromeLayerBuilder::new()
+                .writer(BufWriter::new(file))
+                .include_args(true)
+                .build();
+            layers.push(layer.with_filter(filter_fn(traced)).boxed());
+            Some(guard)
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry().with(layers).init();
+    Ok(TraceGuard { _chrome: chrome })
+}
+
+/// Spans of any level, but only the events that would be logged by default.
+fn traced(metadata: &Metadata<'_>) -> bool {
+    metadata.is_span() || *metadata.level() <= Level::INFO
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn log_style_is_parsed() {
+        assert_eq!("flat".parse::<LogStyle>(), Ok(LogStyle::Flat));
+        assert_eq!("tree".parse::<LogStyle>(), Ok(LogStyle::Tree));
+        assert!("forest".parse::<LogStyle>().is_err());
+    }
+}